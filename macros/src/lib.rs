@@ -0,0 +1,379 @@
+//! The `predicate!` macro, parsing predicates at compile time.
+
+use num::BigRational;
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+
+use predicatechecker::{Predicate, Value, ParseError};
+use predicatechecker::__private::parse_literal;
+
+
+
+/// Create a [Predicate](predicatechecker::Predicate) from the same infix notation as `Predicate::parse`,
+/// parsed at compile time: a predicate that is not valid is a compilation error.
+///
+/// A Rust expression written as `#{expr}` is used as a literal. Its value is converted into the numerical
+/// type of the predicate with [Into], while the other literals are read like `Predicate::parse` does.
+/// The code does not compile if one of them can not be represented by the numerical type, like `0.5` with `i64`
+/// (a literal of an integer type being written without fractional part), `300` with `i8`, or `16777217` with `f32`
+/// (floats only accept the integers they represent exactly, larger ones being written with an exponent like `1e10`).
+/// The supported types are `i8`, `i16`, `i32`, `i64`, `i128`, `f32`, `f64`, `num::BigInt` and `num::BigRational`.
+///
+/// As the delimiters of Rust tokens must be balanced, the intervals of the `in` operator are either closed
+/// (`x in [0, 10]`) or open (`x in (0, 10)`): half-open ones are written with a chained comparison like `0 <= x < 10`.
+///
+/// # Example
+/// ```
+/// use predicatechecker::{Predicate, Implication};
+/// use predicatechecker_macros::predicate;
+///
+/// let limit = 10;
+/// let a: Predicate<f64> = predicate!((x > 5) && (2 * y <= #{limit}));
+/// assert_eq!(a, Predicate::from("(x > 5) && (2 * y <= 10)").unwrap());
+/// assert_eq!(a.implies(&predicate!(y <= 5)), Implication::Total);
+/// ```
+///
+/// Invalid predicates are compilation errors, like an unbalanced parenthesis:
+///
+/// ```compile_fail
+/// use predicatechecker::Predicate;
+/// use predicatechecker_macros::predicate;
+///
+/// let p: Predicate<f64> = predicate!((x > 5) && (x < 10);
+/// ```
+///
+/// an unknown operator:
+///
+/// ```compile_fail
+/// use predicatechecker::Predicate;
+/// use predicatechecker_macros::predicate;
+///
+/// let p: Predicate<f64> = predicate!(x =! 5);
+/// ```
+///
+/// a product of arguments:
+///
+/// ```compile_fail
+/// use predicatechecker::Predicate;
+/// use predicatechecker_macros::predicate;
+///
+/// let p: Predicate<f64> = predicate!(x * y > 2);
+/// ```
+///
+/// or a literal that the numerical type can not represent:
+///
+/// ```compile_fail
+/// use predicatechecker::Predicate;
+/// use predicatechecker_macros::predicate;
+///
+/// let p: Predicate<i64> = predicate!(x > 0.5);
+/// ```
+///
+/// ```compile_fail
+/// use predicatechecker::Predicate;
+/// use predicatechecker_macros::predicate;
+///
+/// let p: Predicate<f64> = predicate!(x > 1/3);
+/// ```
+///
+/// ```compile_fail
+/// use predicatechecker::Predicate;
+/// use predicatechecker_macros::predicate;
+///
+/// let p: Predicate<f32> = predicate!(x > 16777217);
+/// ```
+#[proc_macro]
+pub fn predicate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut source = Source::default();
+
+    let res = source.read(TokenStream::from(input))
+        .and_then(|_| Predicate::<i64>::parse(&source.txt).map_err(|e| source.error(&e)))
+        .map(|p| source.predicate(&p));
+
+    match res {
+        Ok(tokens) => tokens.into(),
+        Err((span, msg)) => quote_spanned!(span=> compile_error!(#msg)).into()
+    }
+}
+
+
+
+
+/// A literal of the predicate, written in place of its index in the parsed string.
+enum Literal {
+    /// Text of a literal, read as a number of the type of the predicate
+    Text(String, Span),
+
+    /// Rust expression converted into the type of the predicate
+    Splice(TokenStream)
+}
+
+
+
+/// Infix string built from the tokens given to the macro, which the parser of `predicatechecker` reads.
+#[derive(Default)]
+struct Source {
+    txt: String,
+
+    /// Location in the string of each token, to report errors at the right place in the code
+    spans: Vec<(usize, usize, Span)>,
+
+    literals: Vec<Literal>,
+
+    /// Whether the last token is an operand, so that a `-` in front of a literal can be part of it
+    after_operand: bool
+}
+
+
+
+impl Source {
+    /// Add a token at the end of the string.
+    fn push(&mut self, t: &str, span: Span) {
+        if !self.txt.is_empty() {self.txt.push(' ')}
+        let start = self.txt.len();
+        self.txt.push_str(t);
+        self.spans.push((start, self.txt.len(), span));
+    }
+
+
+    /// Add a literal, written as its index.
+    fn push_literal(&mut self, literal: Literal, span: Span) {
+        self.push(&self.literals.len().to_string(), span);
+        self.literals.push(literal);
+        self.after_operand = true;
+    }
+
+
+    /// Add the tokens to the string.
+    fn read(&mut self, tokens: TokenStream) -> Result<(), (Span, String)> {
+        let mut tokens = tokens.into_iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            match token {
+                // brackets and braces are the ones of intervals and sets, like `x in [0, 10]` and `x in {1, 2, 5}`
+                TokenTree::Group(g) if g.delimiter() != Delimiter::None => {
+                    let (open, close) = match g.delimiter() {
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::Brace => ("{", "}"),
+                        _ => ("(", ")")
+                    };
+                    self.push(open, g.span_open());
+                    self.after_operand = false;
+                    self.read(g.stream())?;
+                    self.push(close, g.span_close());
+                    self.after_operand = true;
+                },
+
+                // tokens coming from the expansion of another macro
+                TokenTree::Group(g) => self.read(g.stream())?,
+
+                TokenTree::Ident(i) => {
+                    self.push(&i.to_string(), i.span());
+                    self.after_operand = true;
+                },
+
+                TokenTree::Punct(p) if p.as_char() == '#' => match tokens.next() {
+                    Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                        self.push_literal(Literal::Splice(g.stream()), g.span());
+                    },
+                    _ => return Err((p.span(), "Expected a Rust expression between braces after '#'".to_string()))
+                },
+
+                TokenTree::Punct(p) => {
+                    // an operator can be made of several characters, like `>=`
+                    let mut op = p.as_char().to_string();
+                    let mut joint = p.spacing() == proc_macro2::Spacing::Joint;
+                    while joint {
+                        match tokens.peek() {
+                            Some(TokenTree::Punct(q)) if q.as_char() != '#' => {
+                                op.push(q.as_char());
+                                joint = q.spacing() == proc_macro2::Spacing::Joint;
+                                tokens.next();
+                            },
+                            _ => break
+                        }
+                    }
+
+                    match tokens.peek() {
+                        Some(TokenTree::Literal(l)) if op == "-" && !self.after_operand => {
+                            let l = l.clone();
+                            tokens.next();
+                            self.read_literal(format!("-{l}"), p.span(), &mut tokens)?;
+                        },
+                        _ => {
+                            self.push(&op, p.span());
+                            self.after_operand = false;
+                        }
+                    }
+                },
+
+                TokenTree::Literal(l) => self.read_literal(l.to_string(), l.span(), &mut tokens)?
+            }
+        }
+
+        Ok(())
+    }
+
+
+    /// Add a literal, which can be a fraction like `1/3` (the parser has no division operator).
+    fn read_literal(&mut self, mut txt: String, span: Span, tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>) -> Result<(), (Span, String)> {
+        if let Some(TokenTree::Punct(p)) = tokens.peek() {
+            if p.as_char() == '/' {
+                tokens.next();
+                match tokens.next() {
+                    Some(TokenTree::Literal(l)) => txt = format!("{txt}/{l}"),
+                    _ => return Err((span, format!("Expected a denominator after '{txt}/'")))
+                }
+            }
+        }
+
+        // the numerical type of the predicate is not known here, so any literal readable as a number is accepted
+        if parse_literal::<f64>(&txt).is_none() && parse_literal::<BigRational>(&txt).is_none() {
+            return Err((span, format!("Unexpected token '{txt}'")))
+        }
+
+        self.push_literal(Literal::Text(txt.clone(), span), span);
+        Ok(())
+    }
+
+
+    /// Return the location in the code of an error of the parser, with its description.
+    fn error(&self, e: &ParseError) -> (Span, String) {
+        let start = e.span().start;
+        let span = self.spans.iter()
+            .find(|(_, end, _)| *end > start)
+            .or(self.spans.last())
+            .map(|(_, _, s)| *s)
+            .unwrap_or_else(Span::call_site);
+
+        (span, e.message())
+    }
+
+
+    /// Return the code creating the predicate, with the literals written by their index.
+    fn predicate(&self, p: &Predicate<i64>) -> TokenStream {
+        let krate = quote!(::predicatechecker);
+        let boxed = |p: &Predicate<i64>| {
+            let p = self.predicate(p);
+            quote!(::std::boxed::Box::new(#p))
+        };
+
+        match p {
+            Predicate::True => quote!(#krate::Predicate::True),
+            Predicate::False => quote!(#krate::Predicate::False),
+            Predicate::BoolArg(a) => quote!(#krate::Predicate::BoolArg(::std::string::String::from(#a))),
+
+            Predicate::LowerThan(v1, v2) => self.comparison(quote!(LowerThan), v1, v2),
+            Predicate::LowerEqual(v1, v2) => self.comparison(quote!(LowerEqual), v1, v2),
+            Predicate::GreaterThan(v1, v2) => self.comparison(quote!(GreaterThan), v1, v2),
+            Predicate::GreaterEqual(v1, v2) => self.comparison(quote!(GreaterEqual), v1, v2),
+            Predicate::Equal(v1, v2) => self.comparison(quote!(Equal), v1, v2),
+            Predicate::In(v, set) => self.membership(quote!(In), v, set),
+            Predicate::NotIn(v, set) => self.membership(quote!(NotIn), v, set),
+
+            Predicate::Not(p) => {
+                let p = boxed(p);
+                quote!(#krate::Predicate::Not(#p))
+            },
+            Predicate::And(p1, p2) => {
+                let (p1, p2) = (boxed(p1), boxed(p2));
+                quote!(#krate::Predicate::And(#p1, #p2))
+            },
+            Predicate::Or(p1, p2) => {
+                let (p1, p2) = (boxed(p1), boxed(p2));
+                quote!(#krate::Predicate::Or(#p1, #p2))
+            },
+            Predicate::Implies(p1, p2) => {
+                let (p1, p2) = (boxed(p1), boxed(p2));
+                quote!(#krate::Predicate::Implies(#p1, #p2))
+            },
+            Predicate::Iff(p1, p2) => {
+                let (p1, p2) = (boxed(p1), boxed(p2));
+                quote!(#krate::Predicate::Iff(#p1, #p2))
+            },
+            Predicate::Xor(p1, p2) => {
+                let (p1, p2) = (boxed(p1), boxed(p2));
+                quote!(#krate::Predicate::Xor(#p1, #p2))
+            }
+        }
+    }
+
+
+    fn comparison(&self, variant: TokenStream, v1: &Value<i64>, v2: &Value<i64>) -> TokenStream {
+        let (v1, v2) = (self.value(v1), self.value(v2));
+        quote!(::predicatechecker::Predicate::#variant(#v1, #v2))
+    }
+
+
+    fn membership(&self, variant: TokenStream, v: &Value<i64>, set: &[i64]) -> TokenStream {
+        let v = self.value(v);
+        let set = set.iter().map(|i| self.literal(*i));
+        quote!(::predicatechecker::Predicate::#variant(#v, ::std::vec![#(#set),*]))
+    }
+
+
+    /// Return the code of the literal written as the given index.
+    fn literal(&self, i: i64) -> TokenStream {
+        match &self.literals[i as usize] {
+            Literal::Text(txt, span) => {
+                let kind = kind(txt);
+                quote_spanned!(*span=> ::predicatechecker::__private::literal::<_, #kind>(#txt))
+            },
+            Literal::Splice(expr) => quote!(::std::convert::Into::into({#expr}))
+        }
+    }
+
+
+    /// Return the code creating the value, with the literals written by their index.
+    fn value(&self, v: &Value<i64>) -> TokenStream {
+        let krate = quote!(::predicatechecker);
+        let boxed = |v: &Value<i64>| {
+            let v = self.value(v);
+            quote!(::std::boxed::Box::new(#v))
+        };
+
+        match v {
+            Value::Arg(a) => quote!(#krate::Value::Arg(::std::string::String::from(#a))),
+
+            Value::Literal(i) => {
+                let x = self.literal(*i);
+                quote!(#krate::Value::Literal(#x))
+            },
+
+            Value::Add(v1, v2) => {
+                let (v1, v2) = (boxed(v1), boxed(v2));
+                quote!(#krate::Value::Add(#v1, #v2))
+            },
+            Value::Sub(v1, v2) => {
+                let (v1, v2) = (boxed(v1), boxed(v2));
+                quote!(#krate::Value::Sub(#v1, #v2))
+            },
+            Value::Mul(v1, v2) => {
+                let (v1, v2) = (boxed(v1), boxed(v2));
+                quote!(#krate::Value::Mul(#v1, #v2))
+            },
+            Value::Neg(v) => {
+                let v = boxed(v);
+                quote!(#krate::Value::Neg(#v))
+            }
+        }
+    }
+}
+
+
+
+
+/// Return the kind of a literal, which the numerical type of the predicate must be able to represent
+/// (see `predicatechecker::__private::Represents`).
+fn kind(txt: &str) -> TokenStream {
+    let private = quote!(::predicatechecker::__private);
+
+    if let Ok(x) = txt.parse::<i128>() {
+        let bits = [8, 16, 25, 32, 54, 64].into_iter().find(|b| -(1 << (b - 1)) <= x && x < 1 << (b - 1)).unwrap_or(128_u32);
+        quote!(#private::Integer<#bits>)
+    }
+    else if txt.strip_prefix('-').unwrap_or(txt).chars().all(|c| c.is_ascii_digit()) {quote!(#private::BigInteger)}
+    else if parse_literal::<BigRational>(txt).is_none() {quote!(#private::Float)}
+    else if parse_literal::<f64>(txt).is_none() {quote!(#private::Fraction)}
+    else {quote!(#private::Decimal)}
+}
@@ -0,0 +1,69 @@
+//! Predicates parsed at compile time.
+
+use num::{BigRational, BigInt};
+use predicatechecker::{Predicate, Value};
+use predicatechecker_macros::predicate;
+
+
+
+#[test]
+fn same_as_parser() {
+    let cases: Vec<(Predicate<f64>, &str)> = vec![
+        (predicate!(x > 5), "x > 5"),
+        (predicate!((x >= 1.5) && !(y == -2) || enabled), "((x >= 1.5) && ! (y == -2)) || enabled"),
+        (predicate!(2 * (x - y) <= -z + 3), "2 * (x - y) <= - z + 3"),
+        (predicate!(true && (x < 1e3)), "true && (x < 1e3)"),
+        (predicate!(not a || x != 1 || 0 <= y < 10), "not a || x != 1 || 0 <= y < 10"),
+        (predicate!(x in [-1, 5] && y in (0, #{2.5})), "x in [-1, 5] && y in (0, 2.5)"),
+        (predicate!(x in {1, -2, #{3}} || y not in {}), "x in {1, -2, 3} || y not in {}"),
+        (predicate!(premium => (discount >= 10) <=> a xor b), "premium => (discount >= 10) <=> a xor b"),
+    ];
+
+    for (p, txt) in cases {
+        assert_eq!(p, Predicate::from(txt).unwrap());
+    }
+}
+
+
+#[test]
+fn splices() {
+    let limit = 10;
+    let p: Predicate<f64> = predicate!(x > #{limit} - #{limit as f64 / 4.0});
+    assert_eq!(p, Predicate::from("x > 10 - 2.5").unwrap());
+
+    let third = BigRational::new(BigInt::from(1), BigInt::from(3));
+    let p: Predicate<BigRational> = predicate!(x < #{third.clone()});
+    assert_eq!(p, Predicate::LowerThan(Value::Arg("x".to_string()), Value::Literal(third)));
+}
+
+
+#[test]
+fn exact_literals() {
+    let p: Predicate<BigRational> = predicate!((x <= 0.1 + 0.2) && (y > 1/3));
+    assert_eq!(p, Predicate::parse("(x <= 0.1 + 0.2) && (y > 1/3)").unwrap());
+
+    let p: Predicate<i64> = predicate!(x >= 9007199254740993);
+    assert_eq!(p, Predicate::parse("x >= 9007199254740993").unwrap());
+}
+
+
+#[test]
+fn types_of_literals() {
+    let p: Predicate<i8> = predicate!((x > -128) && (x < 127));
+    assert_eq!(p, Predicate::parse("(x > -128) && (x < 127)").unwrap());
+
+    let p: Predicate<i128> = predicate!(x < 170141183460469231731687303715884105727);
+    assert_eq!(p, Predicate::parse("x < 170141183460469231731687303715884105727").unwrap());
+
+    let p: Predicate<BigInt> = predicate!(x < 1000000000000000000000000000000000000000);
+    assert_eq!(p, Predicate::parse("x < 1000000000000000000000000000000000000000").unwrap());
+
+    let p: Predicate<f32> = predicate!((x < 1e3) && (x > 0.5));
+    assert_eq!(p, Predicate::parse("(x < 1e3) && (x > 0.5)").unwrap());
+
+    // the largest integers that the floats represent exactly
+    let p: Predicate<f32> = predicate!((x > -16777216) && (x < 16777215));
+    assert_eq!(p, Predicate::parse("(x > -16777216) && (x < 16777215)").unwrap());
+    let p: Predicate<f64> = predicate!(x < 9007199254740991);
+    assert_eq!(p, Predicate::parse("x < 9007199254740991").unwrap());
+}
@@ -0,0 +1,53 @@
+//! Arithmetic operations that detect when their result can not be represented by the numerical type.
+
+use num::{BigInt, BigUint, CheckedAdd, CheckedMul, CheckedSub, Integer};
+use num::rational::Ratio;
+
+
+
+/// Numerical types whose operations can overflow, like the primitive integer types, and can report it.
+///
+/// Floating-point numbers never overflow (their result is infinite), nor do `BigInt` and `BigRational`.
+pub trait Checked: Sized {
+    /// Return self + other, or None if the result can not be represented.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// Return self - other, or None if the result can not be represented.
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    /// Return self * other, or None if the result can not be represented.
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+}
+
+
+macro_rules! checked_with_num {
+    ($($t:ty),*) => {$(
+        impl Checked for $t {
+            fn checked_add(&self, other: &Self) -> Option<Self> {CheckedAdd::checked_add(self, other)}
+            fn checked_sub(&self, other: &Self) -> Option<Self> {CheckedSub::checked_sub(self, other)}
+            fn checked_mul(&self, other: &Self) -> Option<Self> {CheckedMul::checked_mul(self, other)}
+        }
+    )*};
+}
+
+checked_with_num!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, BigInt, BigUint);
+
+
+macro_rules! checked_float {
+    ($($t:ty),*) => {$(
+        impl Checked for $t {
+            fn checked_add(&self, other: &Self) -> Option<Self> {Some(self + other)}
+            fn checked_sub(&self, other: &Self) -> Option<Self> {Some(self - other)}
+            fn checked_mul(&self, other: &Self) -> Option<Self> {Some(self * other)}
+        }
+    )*};
+}
+
+checked_float!(f32, f64);
+
+
+impl<T: Clone + Integer + CheckedAdd + CheckedSub + CheckedMul> Checked for Ratio<T> {
+    fn checked_add(&self, other: &Self) -> Option<Self> {CheckedAdd::checked_add(self, other)}
+    fn checked_sub(&self, other: &Self) -> Option<Self> {CheckedSub::checked_sub(self, other)}
+    fn checked_mul(&self, other: &Self) -> Option<Self> {CheckedMul::checked_mul(self, other)}
+}
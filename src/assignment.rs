@@ -0,0 +1,103 @@
+//! Values given to the arguments of a predicate.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use num::Num;
+
+
+
+/// Source of the values of the arguments of a [Predicate](crate::Predicate), used to evaluate it.
+pub trait Environment<T> {
+    /// Return the numerical value of an argument, or None if it has none.
+    fn get_value(&self, arg: &str) -> Option<T>;
+
+    /// Return the truth value of a boolean argument, or None if it has none.
+    fn get_bool(&self, arg: &str) -> Option<bool>;
+}
+
+
+
+
+/// Values of the arguments of a [Predicate](crate::Predicate):
+/// numerical values for the arguments used in comparisons, and truth values for the boolean arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment<T: Num> {
+    values: HashMap<String, T>,
+    bools: HashMap<String, bool>
+}
+
+
+
+impl<T: Num> Default for Assignment<T> {
+    fn default() -> Self {
+        Assignment::new()
+    }
+}
+
+
+
+impl<T: Num> Assignment<T> {
+    /// Create an assignment with no values.
+    pub fn new() -> Assignment<T> {
+        Assignment { values: HashMap::new(), bools: HashMap::new() }
+    }
+
+
+    /// Give a numerical value to an argument.
+    pub fn set_value(&mut self, arg: &str, value: T) {
+        self.values.insert(arg.to_string(), value);
+    }
+
+    /// Give a truth value to a boolean argument.
+    pub fn set_bool(&mut self, arg: &str, value: bool) {
+        self.bools.insert(arg.to_string(), value);
+    }
+
+
+    /// Return the numerical value of an argument, if it has one.
+    pub fn get_value(&self, arg: &str) -> Option<&T> {
+        self.values.get(arg)
+    }
+
+    /// Return the truth value of a boolean argument, if it has one.
+    pub fn get_bool(&self, arg: &str) -> Option<bool> {
+        self.bools.get(arg).copied()
+    }
+
+
+    /// Return the arguments with a numerical value, and their value.
+    pub fn values(&self) -> impl Iterator<Item = (&String, &T)> {
+        self.values.iter()
+    }
+
+    /// Return the boolean arguments with a truth value, and their value.
+    pub fn bools(&self) -> impl Iterator<Item = (&String, bool)> {
+        self.bools.iter().map(|(a, b)| (a, *b))
+    }
+}
+
+
+
+impl<T: Num + Clone> Environment<T> for Assignment<T> {
+    fn get_value(&self, arg: &str) -> Option<T> {
+        self.values.get(arg).cloned()
+    }
+
+    fn get_bool(&self, arg: &str) -> Option<bool> {
+        self.bools.get(arg).copied()
+    }
+}
+
+
+
+/// A map only gives numerical values, so it can only be used with predicates that have no boolean arguments.
+impl<T: Num + Clone, S: BuildHasher> Environment<T> for HashMap<String, T, S> {
+    fn get_value(&self, arg: &str) -> Option<T> {
+        self.get(arg).cloned()
+    }
+
+    fn get_bool(&self, _: &str) -> Option<bool> {
+        None
+    }
+}
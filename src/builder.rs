@@ -0,0 +1,262 @@
+//! Construction of predicates with Rust expressions.
+
+use std::ops::{BitAnd, BitOr, BitXor, Not, Add, Sub, Mul, Neg};
+
+use num::Num;
+
+use crate::predicate::{Predicate, Value};
+
+
+
+/// Anything that can be used as a [Value] when building a predicate:
+/// a [Var], a [Value], or a primitive number that `T` represents exactly.
+pub trait IntoValue<T: Num + PartialOrd> {
+    fn into_value(self) -> Value<T>;
+}
+
+
+impl<T: Num + PartialOrd> IntoValue<T> for Value<T> {
+    fn into_value(self) -> Value<T> {
+        self
+    }
+}
+
+
+impl<T: Num + PartialOrd> IntoValue<T> for Var {
+    fn into_value(self) -> Value<T> {
+        Value::Arg(self.0)
+    }
+}
+
+
+/// Implement [IntoValue] for primitive numbers, when `T` can represent all of their values exactly
+/// (`5` and `2.5` are values of a `Predicate<f64>`, but `2.5` is not one of a `Predicate<i64>`):
+///
+/// ```compile_fail
+/// use predicatechecker::{Predicate, var};
+///
+/// let p: Predicate<i64> = var("x").lt(5.5);
+/// ```
+///
+/// The literals of other types, like `num::BigRational`, are given as a [Value::Literal].
+macro_rules! primitive_into_value {
+    ($($t:ty),*) => {$(
+        impl<T: Num + PartialOrd + From<$t>> IntoValue<T> for $t {
+            fn into_value(self) -> Value<T> {
+                Value::Literal(T::from(self))
+            }
+        }
+    )*};
+}
+
+primitive_into_value!(i32, i64, u32, u64, f32, f64);
+
+
+
+
+/// An argument of a predicate, used to build comparisons.
+///
+/// # Example
+/// ```
+/// use predicatechecker::{Predicate, var, flag};
+///
+/// let p: Predicate<f64> = var("x").gt(5) & var("y").le(3) | !flag("enabled");
+/// assert_eq!(p, Predicate::from("(x > 5 && y <= 3) || ! enabled").unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Var(String);
+
+
+
+/// Return the argument with the given name, to be compared with other values.
+pub fn var(name: &str) -> Var {
+    Var(name.to_string())
+}
+
+
+/// Return the predicate which is true when the given boolean argument is.
+pub fn flag<T: Num + PartialOrd>(name: &str) -> Predicate<T> {
+    Predicate::BoolArg(name.to_string())
+}
+
+
+
+impl Var {
+    /// Return the name of the argument.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// Return the argument as a [Value], to be used in arithmetic expressions.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::{Predicate, var};
+    ///
+    /// let p: Predicate<f64> = (var("x").value() * 2 + var("y")).le(10);
+    /// assert_eq!(p, Predicate::from("x * 2 + y <= 10").unwrap());
+    /// ```
+    pub fn value<T: Num + PartialOrd>(self) -> Value<T> {
+        Value::Arg(self.0)
+    }
+
+    /// Return the predicate `self < other`.
+    pub fn lt<T: Num + PartialOrd>(self, other: impl IntoValue<T>) -> Predicate<T> {
+        self.value().lt(other)
+    }
+
+    /// Return the predicate `self <= other`.
+    pub fn le<T: Num + PartialOrd>(self, other: impl IntoValue<T>) -> Predicate<T> {
+        self.value().le(other)
+    }
+
+    /// Return the predicate `self > other`.
+    pub fn gt<T: Num + PartialOrd>(self, other: impl IntoValue<T>) -> Predicate<T> {
+        self.value().gt(other)
+    }
+
+    /// Return the predicate `self >= other`.
+    pub fn ge<T: Num + PartialOrd>(self, other: impl IntoValue<T>) -> Predicate<T> {
+        self.value().ge(other)
+    }
+
+    /// Return the predicate `self == other`.
+    pub fn equals<T: Num + PartialOrd>(self, other: impl IntoValue<T>) -> Predicate<T> {
+        self.value().equals(other)
+    }
+
+    /// Return the predicate `!(self == other)`.
+    pub fn not_equals<T: Num + PartialOrd>(self, other: impl IntoValue<T>) -> Predicate<T> {
+        self.value().not_equals(other)
+    }
+
+    /// Return the predicate `self in {set}`.
+    pub fn is_in<T: Num + PartialOrd>(self, set: impl IntoIterator<Item = T>) -> Predicate<T> {
+        self.value().is_in(set)
+    }
+
+    /// Return the predicate `self not in {set}`.
+    pub fn not_in<T: Num + PartialOrd>(self, set: impl IntoIterator<Item = T>) -> Predicate<T> {
+        self.value().not_in(set)
+    }
+}
+
+
+
+impl<T: Num + PartialOrd> Value<T> {
+    /// Return the predicate `self < other`.
+    pub fn lt(self, other: impl IntoValue<T>) -> Predicate<T> {
+        Predicate::LowerThan(self, other.into_value())
+    }
+
+    /// Return the predicate `self <= other`.
+    pub fn le(self, other: impl IntoValue<T>) -> Predicate<T> {
+        Predicate::LowerEqual(self, other.into_value())
+    }
+
+    /// Return the predicate `self > other`.
+    pub fn gt(self, other: impl IntoValue<T>) -> Predicate<T> {
+        Predicate::GreaterThan(self, other.into_value())
+    }
+
+    /// Return the predicate `self >= other`.
+    pub fn ge(self, other: impl IntoValue<T>) -> Predicate<T> {
+        Predicate::GreaterEqual(self, other.into_value())
+    }
+
+    /// Return the predicate `self == other`.
+    pub fn equals(self, other: impl IntoValue<T>) -> Predicate<T> {
+        Predicate::Equal(self, other.into_value())
+    }
+
+    /// Return the predicate `!(self == other)`.
+    pub fn not_equals(self, other: impl IntoValue<T>) -> Predicate<T> {
+        !self.equals(other)
+    }
+
+    /// Return the predicate `self in {set}`.
+    pub fn is_in(self, set: impl IntoIterator<Item = T>) -> Predicate<T> {
+        Predicate::In(self, set.into_iter().collect())
+    }
+
+    /// Return the predicate `self not in {set}`.
+    pub fn not_in(self, set: impl IntoIterator<Item = T>) -> Predicate<T> {
+        Predicate::NotIn(self, set.into_iter().collect())
+    }
+}
+
+
+
+
+impl<T: Num + PartialOrd> BitAnd for Predicate<T> {
+    type Output = Predicate<T>;
+
+    fn bitand(self, rhs: Predicate<T>) -> Predicate<T> {
+        Predicate::And(Box::new(self), Box::new(rhs))
+    }
+}
+
+
+impl<T: Num + PartialOrd> BitOr for Predicate<T> {
+    type Output = Predicate<T>;
+
+    fn bitor(self, rhs: Predicate<T>) -> Predicate<T> {
+        Predicate::Or(Box::new(self), Box::new(rhs))
+    }
+}
+
+
+impl<T: Num + PartialOrd> BitXor for Predicate<T> {
+    type Output = Predicate<T>;
+
+    fn bitxor(self, rhs: Predicate<T>) -> Predicate<T> {
+        Predicate::Xor(Box::new(self), Box::new(rhs))
+    }
+}
+
+
+impl<T: Num + PartialOrd> Not for Predicate<T> {
+    type Output = Predicate<T>;
+
+    fn not(self) -> Predicate<T> {
+        Predicate::Not(Box::new(self))
+    }
+}
+
+
+
+
+impl<T: Num + PartialOrd, V: IntoValue<T>> Add<V> for Value<T> {
+    type Output = Value<T>;
+
+    fn add(self, rhs: V) -> Value<T> {
+        Value::Add(Box::new(self), Box::new(rhs.into_value()))
+    }
+}
+
+
+impl<T: Num + PartialOrd, V: IntoValue<T>> Sub<V> for Value<T> {
+    type Output = Value<T>;
+
+    fn sub(self, rhs: V) -> Value<T> {
+        Value::Sub(Box::new(self), Box::new(rhs.into_value()))
+    }
+}
+
+
+impl<T: Num + PartialOrd, V: IntoValue<T>> Mul<V> for Value<T> {
+    type Output = Value<T>;
+
+    fn mul(self, rhs: V) -> Value<T> {
+        Value::Mul(Box::new(self), Box::new(rhs.into_value()))
+    }
+}
+
+
+impl<T: Num + PartialOrd> Neg for Value<T> {
+    type Output = Value<T>;
+
+    fn neg(self) -> Value<T> {
+        Value::Neg(Box::new(self))
+    }
+}
@@ -0,0 +1,247 @@
+//! Measure of the proportion of the values verifying a predicate which also verify another one.
+//!
+//! The values of each argument are split into cells, on which every comparison of the predicates has the same
+//! truth value: the truth value of the predicates is then the same on the product of cells of their arguments,
+//! and the set of values verifying them is measured by adding the measures of the products on which they are true.
+//! This only works if each comparison uses a single argument.
+
+use std::collections::HashSet;
+use std::fmt::{Display, Debug};
+
+use num::{Num, Signed, ToPrimitive};
+
+use crate::arithmetic::Checked;
+use crate::assignment::Assignment;
+use crate::domain::Domain;
+use crate::predicate::Predicate;
+use crate::schema::Schema;
+use crate::solver;
+
+
+
+/// Measure of a set of values of the arguments: its number of values if it is finite (`dimension` 0), otherwise
+/// its length, area, volume... (`dimension` 1, 2, 3...) multiplied by the number of values of the other arguments.
+#[derive(Debug, Clone, Copy)]
+struct Measure {
+    dimension: usize,
+    size: f64
+}
+
+
+impl Measure {
+    const EMPTY: Measure = Measure { dimension: 0, size: 0.0 };
+    const POINT: Measure = Measure { dimension: 0, size: 1.0 };
+
+    /// Return the measure of the union of two disjoint sets, the one of lower dimension being negligible.
+    fn union(self, other: Measure) -> Measure {
+        if other.size == 0.0 || (self.size != 0.0 && other.dimension < self.dimension) {self}
+        else if self.size == 0.0 || other.dimension > self.dimension {other}
+        else {Measure { dimension: self.dimension, size: self.size + other.size }}
+    }
+
+    /// Return the measure of the product of two sets.
+    fn product(self, other: Measure) -> Measure {
+        if self.size == 0.0 || other.size == 0.0 {Measure::EMPTY}
+        else {Measure { dimension: self.dimension + other.dimension, size: self.size * other.size }}
+    }
+}
+
+
+
+/// Return the measure of a Domain: the length of its intervals, or its number of values if it has no length.
+fn measure<T: Num + PartialOrd + Clone + ToPrimitive + Display>(d: &Domain<T>) -> Measure {
+    let length = |l: &T, g: &T| (g.clone() - l.clone()).to_f64().unwrap_or(f64::INFINITY);
+
+    d.parts.iter().map(|i| match (&i.lower, &i.greater) {
+        (Some(l), Some(g)) if d.integer => Measure { dimension: 0, size: length(l, g) + 1.0 },
+        (Some(l), Some(g)) if l == g => Measure::POINT,
+        (Some(l), Some(g)) => Measure { dimension: 1, size: length(l, g) },
+        _ => Measure { dimension: !d.integer as usize, size: f64::INFINITY }
+    }).fold(Measure::EMPTY, Measure::union)
+}
+
+
+
+/// Value of an argument on a cell.
+enum Sample<T> {
+    Bool(bool),
+    Number(T)
+}
+
+
+/// Cells of the values of an argument, with a value of each cell and its measure.
+type Cells<T> = Vec<(Sample<T>, Measure)>;
+
+
+
+/// Add the comparisons of the predicate to `comparisons` and its boolean arguments to `bools`.
+/// Return None if a comparison uses several arguments.
+fn collect<'a, T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(p: &'a Predicate<T>, comparisons: &mut Vec<(String, &'a Predicate<T>)>, bools: &mut HashSet<String>) -> Option<()> {
+    match p {
+        Predicate::True | Predicate::False => (),
+        Predicate::BoolArg(a) => {bools.insert(a.clone());},
+        Predicate::Not(p) => collect(p, comparisons, bools)?,
+        Predicate::And(p1, p2) | Predicate::Or(p1, p2) | Predicate::Implies(p1, p2) | Predicate::Iff(p1, p2) | Predicate::Xor(p1, p2) => {
+            collect(p1, comparisons, bools)?;
+            collect(p2, comparisons, bools)?;
+        },
+        _ => {
+            let args = p.get_arguments();
+            if args.len() > 1 {return None}
+            comparisons.extend(args.into_iter().map(|a| (a, p)));
+        }
+    }
+    Some(())
+}
+
+
+/// Return the cells of the values allowed by the schema of each argument of the predicate,
+/// or None if a comparison uses several arguments or can not be reasoned about.
+fn cells<T>(p: &Predicate<T>, schema: &Schema<T>) -> Option<Vec<(String, Cells<T>)>>
+where T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked
+{
+    let mut comparisons = vec![];
+    let mut bools = HashSet::new();
+    collect(p, &mut comparisons, &mut bools)?;
+
+    let mut res = vec![];
+    for a in p.get_arguments() {
+        let compared: Vec<&Predicate<T>> = comparisons.iter().filter(|(b, _)| *b == a).map(|(_, p)| *p).collect();
+
+        if bools.contains(&a) || schema.get_type(&a).is_some_and(|t| t.is_bool()) {
+            if !compared.is_empty() {return None}
+            res.push((a, vec![(Sample::Bool(true), Measure::POINT), (Sample::Bool(false), Measure::POINT)]));
+            continue;
+        }
+
+        let mut domains = vec![match schema.get_range(&a) {
+            Some(range) => range.get_domain_in(&a, schema),
+            None if schema.get_type(&a).is_some_and(|t| t.is_integer()) => Domain::_true().to_integer(),
+            None => Domain::_true()
+        }];
+
+        for c in compared {
+            let (d, not_d) = (solver::get_domain(&[(c, true)], &a, schema), solver::get_domain(&[(c, false)], &a, schema));
+
+            // comparisons that are not reasoned about (not linear, or overflowing `T`) can have both truth values
+            if !Domain::intersection(d.clone(), not_d.clone()).is_empty() {return None}
+
+            domains = domains.into_iter()
+                .flat_map(|cell| [Domain::intersection(cell.clone(), d.clone()), Domain::intersection(cell, not_d.clone())])
+                .filter(|cell| !cell.is_empty())
+                .collect();
+        }
+
+        let cells = domains.iter().map(|cell| Some((Sample::Number(cell.sample()?), measure(cell)))).collect::<Option<_>>()?;
+        res.push((a, cells));
+    }
+
+    Some(res)
+}
+
+
+
+/// Return the measure of the values of the arguments for which each predicate is true, the arguments
+/// having the values of their cells: `assignment` gives the values of the arguments before the ones of `cells`.
+fn measure_cells<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(predicates: &[&Predicate<T>], cells: &[(&String, &Cells<T>)], assignment: &mut Assignment<T>) -> Measure {
+    let Some(((a, first), cells)) = cells.split_first() else {
+        let verified = predicates.iter().all(|p| p.evaluate(assignment) == Ok(true));
+        return if verified {Measure::POINT} else {Measure::EMPTY}
+    };
+
+    let mut res = Measure::EMPTY;
+    for (sample, m) in first.iter() {
+        match sample {
+            Sample::Bool(b) => assignment.set_bool(a, *b),
+            Sample::Number(x) => assignment.set_value(a, x.clone())
+        }
+        res = res.union(m.product(measure_cells(predicates, cells, assignment)));
+    }
+    res
+}
+
+
+/// Return the measure of the values of the arguments for which the predicate is true.
+/// Its conjunctions are split into groups of operands without common arguments, measured separately.
+fn measure_predicate<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(p: &Predicate<T>, cells: &[(String, Cells<T>)]) -> Measure {
+    fn operands<'a, T: Num + PartialOrd>(p: &'a Predicate<T>, res: &mut Vec<&'a Predicate<T>>) {
+        match p {
+            Predicate::And(p1, p2) => {operands(p1, res); operands(p2, res)},
+            p => res.push(p)
+        }
+    }
+    let mut conjunction = vec![];
+    operands(p, &mut conjunction);
+
+    // groups of operands with their arguments
+    let mut groups: Vec<(HashSet<String>, Vec<&Predicate<T>>)> = vec![];
+    for p in conjunction {
+        let (mut args, mut predicates) = (p.get_arguments(), vec![p]);
+        for (a, ps) in std::mem::take(&mut groups) {
+            if a.is_disjoint(&args) {groups.push((a, ps))}
+            else {
+                args.extend(a);
+                predicates.extend(ps);
+            }
+        }
+        groups.push((args, predicates));
+    }
+
+    // the arguments of no group can have any value of their cells
+    let mut res = Measure::POINT;
+    for (a, c) in cells {
+        if !groups.iter().any(|(args, _)| args.contains(a)) {
+            res = res.product(c.iter().fold(Measure::EMPTY, |m, (_, cell)| m.union(*cell)));
+        }
+    }
+
+    for (args, predicates) in groups {
+        let group: Vec<(&String, &Cells<T>)> = cells.iter().filter(|(a, _)| args.contains(a)).map(|(a, c)| (a, c)).collect();
+        res = res.product(measure_cells(&predicates, &group, &mut Assignment::new()));
+    }
+
+    res
+}
+
+
+
+
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> Predicate<T> {
+    /// Return the proportion of the values of the arguments verifying A (self) that also verify B (other),
+    /// between 0 and 1, or None if it can not be measured.
+    ///
+    /// The values are measured by their length, area, volume... (their number for integers and booleans):
+    /// `(x >= 0) && (x <= 10)` is covered at `0.25` by `x < 2.5`. Values of a lower dimension are negligible,
+    /// so `(x > 0) && (x < 1)` is not covered by `x == 0.5`, but `x in {1, 2}` is covered at `0.5` by `x > 1`.
+    ///
+    /// The coverage is None if A is false, if the values verifying A can not be measured because they are unbounded
+    /// (the arguments only used by B included), or if a comparison uses several arguments (like `x < y`)
+    /// or can not be reasoned about.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let a = Predicate::from("(x > 0) && (x < 10) && (y > 0) && (y < 10)").unwrap();
+    /// assert_eq!(a.coverage(&Predicate::from("(x > 5) || (y > 5)").unwrap()), Some(0.75));
+    /// assert_eq!(a.coverage(&Predicate::from("(x < 5) && enabled").unwrap()), Some(0.25));
+    ///
+    /// assert_eq!(Predicate::from("x > 0").unwrap().coverage(&Predicate::from("x > 5").unwrap()), None);
+    /// assert_eq!(a.coverage(&Predicate::from("x < y").unwrap()), None);
+    /// ```
+    pub fn coverage(&self, other: &Predicate<T>) -> Option<f64> {
+        self.coverage_in(other, &Schema::new())
+    }
+
+
+    /// Same as [Predicate::coverage], the arguments only having the values allowed by the schema.
+    pub(crate) fn coverage_in(&self, other: &Predicate<T>, schema: &Schema<T>) -> Option<f64> {
+        let both = Predicate::And(Box::new(self.clone()), Box::new(other.clone()));
+        let cells = cells(&both, schema)?;
+
+        let (a, ab) = (measure_predicate(self, &cells), measure_predicate(&both, &cells));
+        if a.size == 0.0 || a.size.is_infinite() {return None}
+        if ab.dimension < a.dimension {return Some(0.0)}
+        Some(ab.size / a.size)
+    }
+}
@@ -1,18 +1,57 @@
+use std::cmp::Ordering;
 use std::fmt::Display;
 use std::fmt::Debug;
 
-use num::{Num, ToPrimitive};
+use num::{Num, Signed, ToPrimitive};
+
+use crate::arithmetic::Checked;
+use crate::predicate::{Predicate, Value};
+
+
+
+/// Return true if `T` can only represent integers, like the primitive integer types.
+/// The arguments of a predicate over such a type can only have integer values.
+pub fn is_integer<T: Num>() -> bool {
+    (T::one() / (T::one() + T::one())).is_zero()
+}
+
+
+/// Return true if x is not a number (`f64::NAN` for example), i.e. if it can not be compared to itself.
+pub fn is_nan<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+
+/// Return true if x is an infinite value, like `f64::INFINITY` or `f64::NEG_INFINITY`.
+pub fn is_infinite<T: Num + ToPrimitive + Clone>(x: &T) -> bool {
+    // large values of some types can be infinite once converted, but are changed when doubled
+    x.to_f64().is_some_and(f64::is_infinite) && x.clone() * (T::one() + T::one()) == *x
+}
+
+
+/// Return the greatest integer lower or equal to x.
+pub fn floor<T: Num + PartialOrd + Clone>(x: T) -> T {
+    let r = x.clone() % T::one();
+    if r < T::zero() {x - r - T::one()} else {x - r}
+}
+
+
+/// Return the lowest integer greater or equal to x.
+fn ceil<T: Num + PartialOrd + Clone>(x: T) -> T {
+    let r = x.clone() % T::one();
+    if r > T::zero() {x - r + T::one()} else {x - r}
+}
 
 
 
 /// Part of a Domain. Represents a space between two values.
 #[derive(Clone, PartialEq)]
 pub struct Interval<T: Num + Display> {
-    lower: Option<T>,
-    incl_lower: bool,
+    pub(crate) lower: Option<T>,
+    pub(crate) incl_lower: bool,
 
-    greater: Option<T>,
-    incl_greater: bool
+    pub(crate) greater: Option<T>,
+    pub(crate) incl_greater: bool
 }
 
 
@@ -38,6 +77,49 @@ impl<T: Num + PartialOrd + Clone + Debug + Display> Interval<T> {
         Interval { lower, incl_lower, greater, incl_greater }
     }
 
+    /// Return the Interval made of the integers of this one, which has included (integer) bounds,
+    /// or None if it has no integers that `T` can represent (like `x > i64::MAX`).
+    fn to_integer(&self) -> Option<Interval<T>> where T: Checked {
+        let lower = match self.lower.clone() {
+            Some(l) if !self.incl_lower && l == floor(l.clone()) => Some(l.checked_add(&T::one())?),
+            l => l.map(ceil)
+        };
+        let greater = match self.greater.clone() {
+            Some(g) if !self.incl_greater && g == ceil(g.clone()) => Some(g.checked_sub(&T::one())?),
+            g => g.map(floor)
+        };
+
+        let res = Interval::new(lower.clone(), lower.is_some(), greater.clone(), greater.is_some());
+        if res.is_empty() {None} else {Some(res)}
+    }
+
+    /// Return the Interval with only finite bounds, an infinite bound being replaced by no bound,
+    /// or None if no finite value is in the Interval (the bound -∞ is greater than every value for example).
+    /// An Interval with a bound that is not a number has no values.
+    fn finite(self) -> Option<Interval<T>> where T: ToPrimitive {
+        let (mut lower, mut greater) = (self.lower, self.greater);
+        if lower.as_ref().is_some_and(is_nan) || greater.as_ref().is_some_and(is_nan) {return None}
+
+        if lower.as_ref().is_some_and(is_infinite) {
+            if lower > Some(T::zero()) {return None}
+            lower = None;
+        }
+        if greater.as_ref().is_some_and(is_infinite) {
+            if greater < Some(T::zero()) {return None}
+            greater = None;
+        }
+
+        Some(Interval::new(lower.clone(), self.incl_lower && lower.is_some(), greater.clone(), self.incl_greater && greater.is_some()))
+    }
+
+    /// Return true if no value is in the Interval.
+    fn is_empty(&self) -> bool {
+        match (&self.lower, &self.greater) {
+            (Some(l), Some(g)) => l > g || (l == g && !(self.incl_lower && self.incl_greater)),
+            _ => false
+        }
+    }
+
     /// Return the intersection of two [Interval], or None if they don't intersect.
     pub fn intersection(d1: Interval<T>, d2: Interval<T>) -> Option<Interval<T>> {   
 
@@ -70,8 +152,7 @@ impl<T: Num + PartialOrd + Clone + Debug + Display> Interval<T> {
             if lower == greater && !(incl_lower && incl_greater) {return None} // idem
         }
         
-        return Some(Interval::new(lower, incl_lower, greater, incl_greater))
-
+        Some(Interval::new(lower, incl_lower, greater, incl_greater))
     }
 
 
@@ -79,21 +160,24 @@ impl<T: Num + PartialOrd + Clone + Debug + Display> Interval<T> {
 
 
     /// Return the union of two [Interval], or None if they don't intersect/touch.
-    pub fn union(d1: Interval<T>, d2: Interval<T>) -> Option<Interval<T>> {
+    /// If they are made of integers, they also touch when there is no integer between them.
+    pub fn union(d1: Interval<T>, d2: Interval<T>, integer: bool) -> Option<Interval<T>> where T: Checked {
+
+        // i1 touches i2 on the left
+        let touches = |i1: &Interval<T>, i2: &Interval<T>| match (&i1.greater, &i2.lower) {
+            (Some(g), Some(l)) => (g == l && (i1.incl_greater || i2.incl_lower)) || (integer && g.checked_add(&T::one()).as_ref() == Some(l)),
+            _ => false
+        };
 
-        // d1 touches d2 on the left
-        if d1.greater == d2.lower && (d1.incl_greater || d2.incl_lower) {
+        if touches(&d1, &d2) {
             return Some(Interval::new(d1.lower, d1.incl_lower, d2.greater, d2.incl_greater))
         }
-        // d2 touches d1 on the left
-        if d2.greater == d1.lower && (d2.incl_greater || d1.incl_lower) {
+        if touches(&d2, &d1) {
             return Some(Interval::new(d2.lower, d2.incl_lower, d1.greater, d1.incl_greater))
         }
 
         // Not touching and no intersection = no union
-        if Interval::intersection(d1.clone(), d2.clone()).is_none() {
-            return None
-        }
+        Interval::intersection(d1.clone(), d2.clone())?;
 
 
         let (lower, incl_lower) = match (d1.lower, d2.lower) {
@@ -103,7 +187,7 @@ impl<T: Num + PartialOrd + Clone + Debug + Display> Interval<T> {
             (Some(x1), Some(x2)) => {
                 if x1 < x2 {(Some(x1), d1.incl_lower)}
                 else if x1 > x2 {(Some(x2), d2.incl_lower)}
-                else {(Some(x1), d1.incl_lower && d2.incl_lower)}
+                else {(Some(x1), d1.incl_lower || d2.incl_lower)}
             }
         };
 
@@ -114,11 +198,11 @@ impl<T: Num + PartialOrd + Clone + Debug + Display> Interval<T> {
             (Some(x1), Some(x2)) => {
                 if x1 > x2 {(Some(x1), d1.incl_greater)}
                 else if x1 < x2 {(Some(x2), d2.incl_greater)}
-                else {(Some(x1), d1.incl_greater && d2.incl_greater)}
+                else {(Some(x1), d1.incl_greater || d2.incl_greater)}
             }
         };
 
-        return Some(Interval::new(lower, incl_lower, greater, incl_greater))
+        Some(Interval::new(lower, incl_lower, greater, incl_greater))
     }
 }
 
@@ -132,7 +216,8 @@ impl<T: Num + PartialOrd + Clone + Debug + Display> Interval<T> {
 /// Basically, the Domain of a Predicate A describe the numerical space where each value verifies A.
 #[derive(Clone)]
 pub struct Domain<T: Num + Display> {
-    parts: Vec<Interval<T>>
+    pub(crate) parts: Vec<Interval<T>>,
+    pub(crate) integer: bool       // the Domain only contains integers, so its intervals have included integer bounds
 }
 
 
@@ -154,23 +239,45 @@ impl<T: Num + Display> Debug for Domain<T> {
 
 
 
-impl<T: Num + PartialOrd + Clone + ToPrimitive + Display + Debug> Domain<T> {
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> Domain<T> {
 
     // Helpful constructors
     pub fn point(x: T) -> Domain<T> {
-        Domain { parts: vec![Interval::new(Some(x.clone()), true, Some(x), true)] }
+        Domain::from_parts(vec![Interval::new(Some(x.clone()), true, Some(x), true)], is_integer::<T>())
+    }
+    pub fn points(values: Vec<T>) -> Domain<T> {
+        let parts = values.into_iter().map(|x| Interval::new(Some(x.clone()), true, Some(x), true)).collect();
+        Domain::from_parts(parts, is_integer::<T>()).simplified()
     }
     pub fn _true() -> Domain<T> {
-        Domain { parts: vec![Interval::new(None, false, None, false)] }
+        Domain::from_parts(vec![Interval::new(None, false, None, false)], is_integer::<T>())
     }
     pub fn _false() -> Domain<T> {
-        Domain { parts: vec![] }
+        Domain::from_parts(vec![], is_integer::<T>())
     }
 
 
     /// Create a new Domain with one Interval.
+    /// If `T` is an integer type, its bounds are replaced by the closest included integers (`x > 5` is `x >= 6`).
     pub fn new(lower: Option<T>, incl_lower: bool, greater: Option<T>, incl_greater: bool) -> Domain<T> {
-        Domain { parts: vec![Interval::new(lower, incl_lower, greater, incl_greater)] }
+        Domain::from_parts(vec![Interval::new(lower, incl_lower, greater, incl_greater)], is_integer::<T>())
+    }
+
+
+    /// Create a Domain from its Intervals, keeping only their integers if `integer` is true.
+    /// Infinite bounds are replaced by no bound: the values of a Domain are always finite.
+    pub(crate) fn from_parts(parts: Vec<Interval<T>>, integer: bool) -> Domain<T> {
+        let parts = parts.into_iter().filter_map(Interval::finite);
+        if !integer {return Domain { parts: parts.collect(), integer }}
+
+        let parts = parts.filter_map(|i| i.to_integer()).collect();
+        Domain { parts, integer }
+    }
+
+
+    /// Return the Domain made of the integers of this one.
+    pub fn to_integer(&self) -> Domain<T> {
+        Domain::from_parts(self.parts.clone(), true).simplified()
     }
 
 
@@ -179,29 +286,48 @@ impl<T: Num + PartialOrd + Clone + ToPrimitive + Display + Debug> Domain<T> {
         self.parts.is_empty()
     }
 
-    
+
+    /// Return a value of the Domain, or None if it is empty.
+    /// Included bounds are preferred, otherwise the value is taken between the bounds of the first [Interval].
+    pub fn sample(&self) -> Option<T> {
+        let i = self.parts.first()?;
+        let two = T::one() + T::one();
+
+        let x = match (&i.lower, &i.greater) {
+            (Some(l), _) if i.incl_lower => l.clone(),
+            (_, Some(g)) if i.incl_greater => g.clone(),
+            (Some(l), Some(g)) => (l.clone() + g.clone()) / two,
+            (Some(l), None) => l.clone() + T::one(),
+            (None, Some(g)) => g.clone() - T::one(),
+            (None, None) => T::zero()
+        };
+
+        Some(x)
+    }
+
+
     /// Return the intersection of two [Domain].
     /// If they don't intersect, the result is a [Domain] with no [Interval].
     pub fn intersection(d1: Domain<T>, d2: Domain<T>) -> Domain<T> {
-        let mut res = Domain { parts: vec![] };
+        let mut parts = vec![];
 
         for i1 in d1.parts {
             for i2 in d2.parts.clone() {
                 if let Some(i) = Interval::intersection(i1.clone(), i2.clone()) {
-                    res.parts.push(i);
+                    parts.push(i);
                 }
             }
         }
 
-        return res.simplified();
+        Domain::from_parts(parts, d1.integer || d2.integer).simplified()
     }
 
 
     /// Return the union of two [Domain].
     pub fn union(d1: Domain<T>, d2: Domain<T>) -> Domain<T> {
-        let mut res = Domain { parts: d1.parts.clone() };
-        res.parts.extend(d2.parts.clone());
-        return res.simplified();
+        let mut parts = d1.parts;
+        parts.extend(d2.parts);
+        Domain::from_parts(parts, d1.integer || d2.integer).simplified()
     }
 
 
@@ -212,26 +338,32 @@ impl<T: Num + PartialOrd + Clone + ToPrimitive + Display + Debug> Domain<T> {
 
     /// Return the complement of a [Domain].
     pub fn complement(d: Domain<T>) -> Domain<T> {
-        let mut res = Domain { parts: vec![] };
+        let mut res = vec![];
+
+        // the gaps between the intervals are found by going through them in increasing order
+        let parts = d.simplified().sorted_parts();
 
+        // lower bound of the current gap (None is -∞)
         let mut lower = None;
         let mut incl_lower = false;
 
-        for i in d.parts {
-            if let Some(greater) = i.greater {
-                if let Some(lower) = lower {
-                    res.parts.push(Interval::new(Some(lower), incl_lower, Some(greater.clone() ), !i.incl_greater));
-                }
-                lower = Some(greater);
-                incl_lower = !i.incl_greater;
+        for i in parts {
+            if let Some(l) = i.lower {
+                let gap = Interval::new(lower, incl_lower, Some(l), !i.incl_lower);
+                if !gap.is_empty() {res.push(gap);}
             }
-        }
 
-        if let Some(lower) = lower {
-            res.parts.push(Interval::new(Some(lower), incl_lower, None, false));
+            match i.greater {
+                Some(g) => {
+                    lower = Some(g);
+                    incl_lower = !i.incl_greater;
+                },
+                None => return Domain::from_parts(res, d.integer)
+            }
         }
 
-        return res.simplified();
+        res.push(Interval::new(lower, incl_lower, None, false));
+        Domain::from_parts(res, d.integer)
     }
 
 
@@ -240,31 +372,82 @@ impl<T: Num + PartialOrd + Clone + ToPrimitive + Display + Debug> Domain<T> {
 
 
 
+    /// Return the [Interval]s of the Domain, sorted by increasing lower bound.
+    fn sorted_parts(&self) -> Vec<Interval<T>> {
+        let mut parts = self.parts.clone();
+        parts.sort_by(|i1, i2| match (&i1.lower, &i2.lower) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(x1), Some(x2)) => x1.partial_cmp(x2).unwrap_or(Ordering::Equal)
+        });
+        parts
+    }
+
+
+    /// Return a predicate on the given argument which is true exactly for the values of the Domain.
+    /// A Domain made of several points is a set (`x in {1, 2, 5}`).
+    pub fn to_predicate(&self, arg_name: &str) -> Predicate<T> {
+        let arg = || Value::Arg(arg_name.to_string());
+
+        let parts = self.simplified().sorted_parts();
+        if parts.len() > 1 && parts.iter().all(|i| i.lower.is_some() && i.lower == i.greater) {
+            return Predicate::In(arg(), parts.into_iter().filter_map(|i| i.lower).collect())
+        }
+
+        let parts = parts.into_iter().map(|i| {
+            let lower = i.lower.clone().map(|l| {
+                if i.incl_lower {Predicate::GreaterEqual(arg(), Value::Literal(l))}
+                else {Predicate::GreaterThan(arg(), Value::Literal(l))}
+            });
+            let greater = i.greater.clone().map(|g| {
+                if i.incl_greater {Predicate::LowerEqual(arg(), Value::Literal(g))}
+                else {Predicate::LowerThan(arg(), Value::Literal(g))}
+            });
+
+            match (lower, greater) {
+                _ if i.lower.is_some() && i.lower == i.greater => Predicate::Equal(arg(), Value::Literal(i.lower.unwrap())),
+                (Some(l), Some(g)) => Predicate::And(Box::new(l), Box::new(g)),
+                (Some(p), None) | (None, Some(p)) => p,
+                (None, None) => Predicate::True
+            }
+        });
+
+        parts.reduce(|p1, p2| Predicate::Or(Box::new(p1), Box::new(p2))).unwrap_or(Predicate::False)
+    }
+
+
     /// Return a simplified [Domain] by merging adjacent [Interval]s.
     pub fn simplified(&self) -> Domain<T> {
         if self.parts.is_empty() {return self.clone()}
 
         let mut remaining_parts = self.parts.clone();
 
-        let mut res: Domain<T> = Domain { parts: vec![] };
+        let mut res: Domain<T> = Domain { parts: vec![], integer: self.integer };
+
 
 
+        while let Some(mut current) = remaining_parts.pop() {
 
-        while !remaining_parts.is_empty() {
-            let mut current = remaining_parts.pop().unwrap();
+            // once extended, the current Interval can touch Intervals it did not before
+            let mut merged = true;
+            while merged {
+                merged = false;
 
-            let mut j: isize = remaining_parts.len() as isize - 1;
-            while j >= 0 {
-                if let Some(union) = Interval::union(current.clone(), remaining_parts[j as usize].clone()) {
-                    current = union;
-                    remaining_parts.remove(j as usize);
+                let mut j = 0;
+                while j < remaining_parts.len() {
+                    if let Some(union) = Interval::union(current.clone(), remaining_parts[j].clone(), self.integer) {
+                        current = union;
+                        remaining_parts.remove(j);
+                        merged = true;
+                    }
+                    else {j += 1;}
                 }
-                j-=1;
             }
             res.parts.push(current);
         }
 
-        return res
+        res
     }
 }
 
@@ -290,6 +473,6 @@ impl<T: Num + PartialOrd + Clone + Display> PartialEq for Domain<T> {
             if !found {return false}
         }
 
-        return other_parts.is_empty()
+        other_parts.is_empty()
     }
 }
\ No newline at end of file
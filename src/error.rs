@@ -0,0 +1,175 @@
+//! Errors returned when parsing or evaluating a predicate.
+
+use std::fmt::Display;
+
+
+
+/// Location of a part of the parsed string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Byte offset of the first character
+    pub start: usize,
+    /// Byte offset following the last character
+    pub end: usize,
+
+    /// Line of the first character, starting at 1
+    pub line: usize,
+    /// Column (in characters) of the first character, starting at 1
+    pub column: usize
+}
+
+
+
+impl Span {
+    /// Create the span between two byte offsets of the given string, computing its line and column.
+    pub fn new(txt: &str, start: usize, end: usize) -> Span {
+        let before = &txt[..start];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = txt[line_start..start].chars().count() + 1;
+
+        Span { start, end, line, column }
+    }
+}
+
+
+
+
+/// Error returned when a string is not a valid predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A token is not valid, or not expected at this place.
+    /// An empty token designates the end of the string.
+    UnexpectedToken(String, Span),
+
+    /// A parenthesis is never closed, or closes nothing
+    UnbalancedParenthesis(Span),
+
+    /// An operator lacks one or more of its operands
+    MissingOperand(String, Span),
+
+    /// A symbol looks like an operator but is not a known one
+    UnknownOperator(String, Span),
+
+    /// A product of two values that both depend on arguments (the checker only supports linear expressions)
+    NonLinearProduct(Span),
+
+    /// An argument is not declared by the [Schema](crate::Schema) used to parse the predicate
+    UndeclaredArgument(String, Span),
+
+    /// An argument is used as a boolean but is declared as a number, or the opposite
+    WrongType(String, Span),
+
+    /// A literal is not a number (like `NaN` with `f64`), or a constant can not be represented by the numerical type,
+    /// so it can not be compared
+    NotANumber(Span)
+}
+
+
+
+impl ParseError {
+    /// Return the location of the error in the parsed string
+    pub fn span(&self) -> &Span {
+        match self {
+            ParseError::UnexpectedToken(_, s) => s,
+            ParseError::UnbalancedParenthesis(s) => s,
+            ParseError::MissingOperand(_, s) => s,
+            ParseError::UnknownOperator(_, s) => s,
+            ParseError::NonLinearProduct(s) => s,
+            ParseError::UndeclaredArgument(_, s) => s,
+            ParseError::WrongType(_, s) => s,
+            ParseError::NotANumber(s) => s,
+        }
+    }
+
+
+    /// Return the description of the error, without its location.
+    pub fn message(&self) -> String {
+        match self {
+            ParseError::UnexpectedToken(t, _) if t.is_empty() => "Unexpected end of predicate".to_string(),
+            ParseError::UnexpectedToken(t, _) => format!("Unexpected token '{t}'"),
+            ParseError::UnbalancedParenthesis(_) => "Unbalanced parenthesis".to_string(),
+            ParseError::MissingOperand(op, _) => format!("Missing operand for '{op}'"),
+            ParseError::UnknownOperator(op, _) => format!("Unknown operator '{op}'"),
+            ParseError::NonLinearProduct(_) => "Product of two arguments is not supported".to_string(),
+            ParseError::UndeclaredArgument(a, _) => format!("Argument '{a}' is not declared"),
+            ParseError::WrongType(a, _) => format!("Argument '{a}' is not used according to its type"),
+            ParseError::NotANumber(_) => "Literal is not a number".to_string(),
+        }
+    }
+
+
+    /// Return a description of the error showing the line of the parsed string where it occured,
+    /// with the faulty part underlined.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let txt = "(x > 5) && (x < 10";
+    /// let err = Predicate::from(txt).unwrap_err();
+    /// assert_eq!(err.render(txt), "Unbalanced parenthesis (line 1, column 12)\n(x > 5) && (x < 10\n           ^");
+    /// ```
+    pub fn render(&self, txt: &str) -> String {
+        let span = self.span();
+
+        let line = txt.lines().nth(span.line - 1).unwrap_or("");
+        let line_start = txt.split('\n').take(span.line - 1).map(|l| l.len() + 1).sum::<usize>();
+        let line_end = line_start + line.len();
+
+        // the end of the span is limited to the line, and the caret is always shown
+        let width = txt[span.start.min(line_end)..span.end.min(line_end)].chars().count().max(1);
+
+        format!("{self}\n{line}\n{}{}", " ".repeat(span.column - 1), "^".repeat(width))
+    }
+}
+
+
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let span = self.span();
+        write!(f, "{} (line {}, column {})", self.message(), span.line, span.column)
+    }
+}
+
+
+
+impl std::error::Error for ParseError {}
+
+
+
+
+
+/// Error returned when a predicate cannot be evaluated with the given values of its arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    /// The argument has no value
+    Unbound(String),
+
+    /// The argument is used as a boolean but only has a numerical value, or the opposite
+    WrongType(String),
+
+    /// The compared value (an argument, or an expression like `x - y` with infinite values) is not a number
+    NotANumber(String),
+
+    /// The value of the expression can not be represented by the numerical type (like `x + 1` when `x` is `i64::MAX`)
+    Overflow(String)
+}
+
+
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::Unbound(a) => write!(f, "Argument '{a}' has no value"),
+            EvalError::WrongType(a) => write!(f, "Argument '{a}' does not have a value of the expected type"),
+            EvalError::NotANumber(v) => write!(f, "Value of '{v}' is not a number"),
+            EvalError::Overflow(v) => write!(f, "Value of '{v}' overflows the numerical type"),
+        }
+    }
+}
+
+
+
+impl std::error::Error for EvalError {}
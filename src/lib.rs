@@ -1,4 +1,36 @@
 mod predicate;
+mod assignment;
 mod domain;
+mod arithmetic;
+mod linear;
+mod solver;
 mod parser;
-pub use predicate::{Predicate, Value, Implication};
\ No newline at end of file
+mod error;
+mod normal;
+mod coverage;
+mod schema;
+mod builder;
+mod smtlib;
+mod sql;
+mod literal;
+#[cfg(feature = "serde")]
+mod serialization;
+pub use predicate::{Predicate, Value, Implication};
+pub use assignment::{Assignment, Environment};
+pub use error::{ParseError, EvalError, Span};
+pub use schema::{Schema, Type};
+pub use builder::{Var, IntoValue, var, flag};
+pub use domain::{Domain, Interval};
+pub use arithmetic::Checked;
+pub use sql::Placeholder;
+pub use parser::Dialect;
+#[cfg(feature = "serde")]
+pub use serialization::FORMAT_VERSION;
+
+
+// used by the code generated by the `predicate!` macro of `predicatechecker-macros`
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::parser::parse_literal;
+    pub use crate::literal::{literal, Represents, Integer, BigInteger, Decimal, Float, Fraction};
+}
\ No newline at end of file
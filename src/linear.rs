@@ -0,0 +1,401 @@
+//! Linear expressions and constraints over the arguments of a predicate.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Debug};
+
+use num::{Num, Signed, ToPrimitive};
+
+use crate::arithmetic::Checked;
+use crate::domain::{Domain, is_integer, is_nan, is_infinite, floor};
+use crate::predicate::Value;
+
+
+
+/// Linear combination of arguments plus a constant: `a1*x1 + ... + an*xn + c`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearExpr<T: Num> {
+    coefs: BTreeMap<String, T>,     // null coefficients are never stored
+    constant: T
+}
+
+
+
+
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> LinearExpr<T> {
+    /// Create an expression with no arguments.
+    pub fn constant(x: T) -> LinearExpr<T> {
+        LinearExpr { coefs: BTreeMap::new(), constant: x }
+    }
+
+    /// Create an expression made of a single argument.
+    pub fn arg(name: &str) -> LinearExpr<T> {
+        let mut coefs = BTreeMap::new();
+        coefs.insert(name.to_string(), T::one());
+        LinearExpr { coefs, constant: T::zero() }
+    }
+
+
+    /// Return the linear expression equivalent to a [Value],
+    /// or None if it is not linear (i.e. it contains a product of two arguments).
+    ///
+    /// Infinite coefficients and values that are not numbers (like `inf - inf`) can not be reasoned about either,
+    /// so None is also returned for them, as well as for values overflowing `T`. An infinite constant is kept.
+    pub fn from_value(v: &Value<T>) -> Option<LinearExpr<T>> {
+        let res = match v {
+            Value::Arg(a) => LinearExpr::arg(a),
+            Value::Literal(x) => LinearExpr::constant(x.clone()),
+            Value::Add(v1, v2) => LinearExpr::from_value(v1)?.checked_add(LinearExpr::from_value(v2)?)?,
+            Value::Sub(v1, v2) => LinearExpr::from_value(v1)?.checked_sub(LinearExpr::from_value(v2)?)?,
+            Value::Neg(v) => LinearExpr::from_value(v)?.checked_neg()?,
+            Value::Mul(v1, v2) => {
+                let e1 = LinearExpr::from_value(v1)?;
+                let e2 = LinearExpr::from_value(v2)?;
+
+                if e1.is_constant() {e2.checked_mul(e1.constant)?}
+                else if e2.is_constant() {e1.checked_mul(e2.constant)?}
+                else {return None}
+            }
+        };
+
+        if is_nan(&res.constant) || res.coefs.values().any(|x| is_nan(x) || is_infinite(x)) {return None}
+        Some(res)
+    }
+
+
+    /// Return the coefficient of an argument in the expression (0 if it is not used).
+    pub fn get_coef(&self, arg: &str) -> T {
+        self.coefs.get(arg).cloned().unwrap_or_else(T::zero)
+    }
+
+    /// Return the arguments used by the expression.
+    pub fn get_arguments(&self) -> impl Iterator<Item = &String> {
+        self.coefs.keys()
+    }
+
+    /// Return the expression where the arguments with a known value are replaced by this value,
+    /// or None if the result overflows `T`.
+    pub fn substitute(&self, values: &HashMap<String, T>) -> Option<LinearExpr<T>> {
+        let mut res = LinearExpr::constant(self.constant.clone());
+
+        for (a, x) in &self.coefs {
+            res = match values.get(a) {
+                Some(v) => res.checked_add(LinearExpr::constant(x.checked_mul(v)?))?,
+                None => res.checked_add(LinearExpr::arg(a).checked_mul(x.clone())?)?
+            };
+        }
+
+        Some(res)
+    }
+
+    /// Return true if the expression does not depend on any argument.
+    pub fn is_constant(&self) -> bool {
+        self.coefs.is_empty()
+    }
+
+
+    /// Return the sum of two expressions, or None if a coefficient or the constant overflows `T`.
+    pub fn checked_add(self, other: LinearExpr<T>) -> Option<LinearExpr<T>> {
+        let mut coefs = self.coefs;
+
+        for (a, x) in other.coefs {
+            let sum = coefs.remove(&a).unwrap_or_else(T::zero).checked_add(&x)?;
+            if !sum.is_zero() {coefs.insert(a, sum);}
+        }
+
+        Some(LinearExpr { coefs, constant: self.constant.checked_add(&other.constant)? })
+    }
+
+    /// Return the opposite of the expression, or None if it overflows `T` (like `-i64::MIN`).
+    pub fn checked_neg(self) -> Option<LinearExpr<T>> {
+        self.checked_mul(-T::one())
+    }
+
+    /// Return the difference of two expressions, or None if it overflows `T`.
+    pub fn checked_sub(self, other: LinearExpr<T>) -> Option<LinearExpr<T>> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    /// Return the expression multiplied by a constant, or None if it overflows `T`.
+    pub fn checked_mul(self, k: T) -> Option<LinearExpr<T>> {
+        if k.is_zero() {return Some(LinearExpr::constant(T::zero()))}
+
+        let coefs = self.coefs.into_iter().map(|(a, x)| Some((a, x.checked_mul(&k)?))).collect::<Option<_>>()?;
+        let constant = if self.constant.is_zero() {T::zero()} else {self.constant.checked_mul(&k)?};     // avoids -0 with floats
+        Some(LinearExpr { coefs, constant })
+    }
+}
+
+
+
+/// Return the greatest common divisor of two integers, which is always positive,
+/// or None if it overflows `T` (the greatest common divisor of `i64::MIN` and 0 for example).
+fn gcd<T: Num + PartialOrd + Clone + Checked>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+    while !b.is_zero() {
+        let r = a % b.clone();
+        a = b;
+        b = r;
+    }
+    if a < T::zero() {T::zero().checked_sub(&a)} else {Some(a)}
+}
+
+
+
+
+
+
+/// Linear constraint `expr < 0` (if strict) or `expr <= 0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint<T: Num> {
+    expr: LinearExpr<T>,
+    strict: bool
+}
+
+
+
+
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> Constraint<T> {
+    /// Constructor
+    ///
+    /// The arguments only have finite values, so if the constant of the expression is infinite,
+    /// the constraint does not depend on them (`x - ∞ < 0` is always true).
+    pub fn new(expr: LinearExpr<T>, strict: bool) -> Constraint<T> {
+        if is_infinite(&expr.constant) {return Constraint { expr: LinearExpr::constant(expr.constant), strict }}
+        Constraint { expr, strict }
+    }
+
+
+    /// Return the constraint made as tight as possible, knowing that its arguments can only have integer values.
+    ///
+    /// If the coefficients are integers, `expr` is `s + c` where `s` can only have integer values:
+    /// `expr < 0` is `s + floor(c) + 1 <= 0`, and `a*s' + c <= 0` (`a` being the greatest common divisor
+    /// of the coefficients) is `s' + ceil(c/a) <= 0`. This way, constraints on a single argument give its exact integer bounds.
+    ///
+    /// The constraint is kept as it is if the tightened one can not be represented by `T` (like `x - i64::MAX < 0`).
+    pub fn tightened(&self) -> Constraint<T> {
+        if self.expr.is_constant() || self.expr.coefs.values().any(|x| *x != floor(x.clone())) {return self.clone()}
+
+        let mut c = self.expr.constant.clone();
+        if self.strict {
+            match floor(c).checked_add(&T::one()) {
+                Some(x) => c = x,
+                None => return self.clone()
+            }
+        }
+
+        let a = match self.expr.coefs.values().try_fold(T::zero(), |a, x| gcd(a, x.clone())) {
+            Some(a) => a,
+            None => return self.clone()
+        };
+        if a <= T::one() {
+            return Constraint { expr: LinearExpr { coefs: self.expr.coefs.clone(), constant: c }, strict: false }
+        }
+
+        // ceil(c/a), without relying on the rounding of the division of T
+        let r = c.clone() % a.clone();
+        let q = (c - r.clone()) / a.clone();
+        let constant = if r > T::zero() {q + T::one()} else {q};
+
+        let coefs = self.expr.coefs.iter().map(|(arg, x)| (arg.clone(), x.clone() / a.clone())).collect();
+        Constraint { expr: LinearExpr { coefs, constant }, strict: false }
+    }
+
+
+    /// Return the truth value of the constraint if it does not depend on any argument.
+    pub fn evaluate(&self) -> Option<bool> {
+        if !self.expr.is_constant() {return None}
+
+        if self.strict {Some(self.expr.constant < T::zero())}
+        else {Some(self.expr.constant <= T::zero())}
+    }
+
+
+    /// Return the domain of the values of the given argument where the constraint is true.
+    /// If the constraint depends on other arguments, the domain could be anything.
+    pub fn get_domain(&self, arg_name: &str) -> Domain<T> {
+        if let Some(b) = self.evaluate() {
+            return if b {Domain::_true()} else {Domain::_false()}
+        }
+        if self.expr.coefs.len() > 1 || !self.expr.coefs.contains_key(arg_name) {return Domain::_true()}
+
+        // a*x + c < 0 <=> x < -(c/a) if a > 0, x > c/(-a) if a < 0
+        // with integer types, -(c/a) only overflows for x + MIN < 0, verified by every value,
+        // and c/(-a) is -(c/a) (the division is truncated), which does not overflow when -a does
+        let a = self.expr.get_coef(arg_name);
+        let c = self.expr.constant.clone();
+
+        if a > T::zero() {
+            match T::zero().checked_sub(&(c / a)) {
+                Some(g) => Domain::new(None, false, Some(g), !self.strict),
+                None => Domain::_true()
+            }
+        }
+        else {
+            let l = match T::zero().checked_sub(&a) {
+                Some(b) => c / b,
+                None => T::zero() - c / a
+            };
+            Domain::new(Some(l), !self.strict, None, false)
+        }
+    }
+}
+
+
+
+
+
+
+/// Conjunction of linear constraints, solved using the Fourier-Motzkin elimination.
+#[derive(Debug, Clone)]
+pub struct System<T: Num> {
+    constraints: Vec<Constraint<T>>,
+    integers: HashSet<String>       // arguments that can only have integer values
+}
+
+
+
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> Default for System<T> {
+    fn default() -> Self {
+        System::new()
+    }
+}
+
+
+
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> System<T> {
+    /// Create a system with no constraints.
+    pub fn new() -> System<T> {
+        System { constraints: vec![], integers: HashSet::new() }
+    }
+
+
+    /// Declare that an argument can only have integer values.
+    /// If `T` is an integer type, every argument is already considered this way.
+    pub fn set_integer(&mut self, arg: &str) {
+        self.integers.insert(arg.to_string());
+    }
+
+
+    /// Return true if the argument can only have integer values.
+    fn is_integer(&self, arg: &str) -> bool {
+        is_integer::<T>() || self.integers.contains(arg)
+    }
+
+
+    /// Add a constraint to the system.
+    /// If its arguments can only have integer values, it is tightened first.
+    ///
+    /// A constraint on a single argument that no value of `T` verifies (like `x > i64::MAX`)
+    /// is replaced by a constraint that is always false.
+    pub fn add(&mut self, c: Constraint<T>) {
+        let integer = c.expr.get_arguments().all(|a| self.is_integer(a));
+        let mut c = if integer {c.tightened()} else {c};
+
+        if integer && c.expr.coefs.len() == 1 && c.expr.get_arguments().all(|a| c.get_domain(a).to_integer().is_empty()) {
+            c = Constraint::new(LinearExpr::constant(T::one()), false);
+        }
+        if !self.constraints.contains(&c) {self.constraints.push(c);}
+    }
+
+
+    /// Return a system with no constraints on the given argument,
+    /// verified by the same values of the other arguments than this system.
+    pub fn eliminate(&self, arg: &str) -> System<T> {
+        let mut res = System { constraints: vec![], integers: self.integers.clone() };
+        let mut uppers = vec![];     // constraints with a positive coefficient for arg (upper bounds)
+        let mut lowers = vec![];     // constraints with a negative coefficient for arg (lower bounds)
+
+        for c in &self.constraints {
+            let a = c.expr.get_coef(arg);
+            if a > T::zero() {uppers.push((a, c));}
+            else if a < T::zero() {lowers.push((a, c));}
+            else {res.add(c.clone());}
+        }
+
+        // a*x + r <= 0 (a > 0) and b*x + s <= 0 (b < 0) gives -b*r + a*s <= 0
+        // if it overflows `T`, the combination is skipped: the system is then verified by more values
+        for (a, cu) in &uppers {
+            for (b, cl) in &lowers {
+                let expr = cu.expr.clone().checked_mul(b.clone()).and_then(|e| cl.expr.clone().checked_mul(a.clone())?.checked_sub(e));
+                if let Some(expr) = expr {res.add(Constraint::new(expr, cu.strict || cl.strict));}
+            }
+        }
+
+        res
+    }
+
+
+    /// Return the arguments used by the constraints of the system.
+    pub fn get_arguments(&self) -> Vec<String> {
+        let mut res: Vec<String> = vec![];
+        for c in &self.constraints {
+            for a in c.expr.get_arguments() {
+                if !res.contains(a) {res.push(a.clone());}
+            }
+        }
+        res
+    }
+
+
+    /// Return the arguments used by each constraint of the system.
+    pub fn get_related_arguments(&self) -> impl Iterator<Item = Vec<String>> + '_ {
+        self.constraints.iter().map(|c| c.expr.get_arguments().cloned().collect())
+    }
+
+
+    /// Return true if there are values of the arguments verifying every constraint of the system.
+    pub fn is_feasible(&self) -> bool {
+        let mut system = self.clone();
+        for a in self.get_arguments() {
+            if system.constraints.iter().any(|c| c.evaluate() == Some(false)) {return false}
+            system = system.eliminate(&a);
+        }
+
+        system.constraints.iter().all(|c| c.evaluate() != Some(false))
+    }
+
+
+
+    /// Return the domain of the values of the given argument for which the system can be verified.
+    pub fn get_domain(&self, arg_name: &str) -> Domain<T> {
+        let mut system = self.clone();
+        for a in self.get_arguments() {
+            if a != arg_name {system = system.eliminate(&a);}
+        }
+
+        let res = system.constraints.iter().fold(Domain::_true(), |d, c| Domain::intersection(d, c.get_domain(arg_name)));
+        if self.is_integer(arg_name) {res.to_integer()} else {res}
+    }
+
+
+    /// Return values of the arguments verifying every constraint of the system, or None if there are none.
+    ///
+    /// The arguments are eliminated one after the other, then given a value in the reverse order:
+    /// once the following arguments are known, the constraints remaining before the elimination
+    /// of an argument only depend on it, and define the domain its value is taken from.
+    pub fn solve(&self) -> Option<HashMap<String, T>> {
+        let args = self.get_arguments();
+
+        let mut systems = vec![self.clone()];
+        for a in &args {
+            let s = systems.last().unwrap().eliminate(a);
+            systems.push(s);
+        }
+        if systems.last().unwrap().constraints.iter().any(|c| c.evaluate() == Some(false)) {return None}
+
+        let mut values = HashMap::new();
+        for (a, system) in args.iter().zip(systems.iter()).rev() {
+            let domain = system.constraints.iter()
+                .map(|c| Some(Constraint::new(c.expr.substitute(&values)?, c.strict)))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .map(|c| if self.is_integer(a) {c.tightened()} else {c})
+                .fold(Domain::_true(), |d, c| Domain::intersection(d, c.get_domain(a)));
+
+            let domain = if self.is_integer(a) {domain.to_integer()} else {domain};
+            values.insert(a.clone(), domain.sample()?);
+        }
+
+        Some(values)
+    }
+}
@@ -0,0 +1,63 @@
+//! Literals of the predicates created by the `predicate!` macro of `predicatechecker-macros`.
+//!
+//! The macro does not know the numerical type of the predicate, so it gives each literal a kind,
+//! and the generated code only compiles if the type can represent the literals of this kind.
+
+use std::str::FromStr;
+
+use num::{Num, BigInt, BigRational};
+
+use crate::parser::parse_literal;
+
+
+
+/// Integer literal that a signed integer of `BITS` bits can represent.
+pub struct Integer<const BITS: u32>;
+
+/// Integer literal too large for any primitive integer type.
+pub struct BigInteger;
+
+/// Decimal literal with a fractional part, like `0.5`.
+pub struct Decimal;
+
+/// Literal with an exponent, like `1e3`.
+pub struct Float;
+
+/// Fraction, like `1/3`.
+pub struct Fraction;
+
+
+
+/// Numerical types which can represent the literals of the kind `K`.
+#[diagnostic::on_unimplemented(
+    message = "a literal of the predicate can not be represented by `{Self}`",
+    label = "this literal can not be a `{Self}`"
+)]
+pub trait Represents<K> {}
+
+
+macro_rules! represents {
+    ($($t:ty => $($k:ty),*);*) => {$($(
+        impl Represents<$k> for $t {}
+    )*)*};
+}
+
+represents!(
+    i8 => Integer<8>;
+    i16 => Integer<8>, Integer<16>;
+    i32 => Integer<8>, Integer<16>, Integer<25>, Integer<32>;
+    i64 => Integer<8>, Integer<16>, Integer<25>, Integer<32>, Integer<54>, Integer<64>;
+    i128 => Integer<8>, Integer<16>, Integer<25>, Integer<32>, Integer<54>, Integer<64>, Integer<128>;
+    // floats only represent exactly the integers fitting in their significand (24 bits for f32, 53 for f64) and a sign
+    f32 => Integer<8>, Integer<16>, Integer<25>, Decimal, Float;
+    f64 => Integer<8>, Integer<16>, Integer<25>, Integer<32>, Integer<54>, Decimal, Float;
+    BigInt => Integer<8>, Integer<16>, Integer<25>, Integer<32>, Integer<54>, Integer<64>, Integer<128>, BigInteger;
+    BigRational => Integer<8>, Integer<16>, Integer<25>, Integer<32>, Integer<54>, Integer<64>, Integer<128>, BigInteger, Decimal, Fraction
+);
+
+
+
+/// Return the value of a literal of the kind `K`, which `T` can represent.
+pub fn literal<T: Num + Clone + FromStr + Represents<K>, K>(txt: &str) -> T {
+    parse_literal(txt).expect("the kind of the literal is the one of its text")
+}
@@ -0,0 +1,316 @@
+//! Transformations of predicates into equivalent ones: normal forms and simplification.
+
+use std::fmt::{Display, Debug};
+
+use num::{Num, Signed, ToPrimitive};
+
+use crate::arithmetic::Checked;
+use crate::domain::Domain;
+use crate::predicate::Predicate;
+
+
+
+/// Return the conjunction of the predicates (True if there are none).
+pub(crate) fn conjunction<T: Num + PartialOrd>(predicates: Vec<Predicate<T>>) -> Predicate<T> {
+    predicates.into_iter().reduce(|p1, p2| Predicate::And(Box::new(p1), Box::new(p2))).unwrap_or(Predicate::True)
+}
+
+
+/// Return the disjunction of the predicates (False if there are none).
+pub(crate) fn disjunction<T: Num + PartialOrd>(predicates: Vec<Predicate<T>>) -> Predicate<T> {
+    predicates.into_iter().reduce(|p1, p2| Predicate::Or(Box::new(p1), Box::new(p2))).unwrap_or(Predicate::False)
+}
+
+
+
+
+impl<T: Num + PartialOrd + Clone> Predicate<T> {
+    /// Return an equivalent predicate written with `And`, `Or` and `Not` instead of `Implies`, `Iff` and `Xor`
+    /// at the top of the predicate (their operands are not changed), or None if it is not one of them.
+    pub(crate) fn desugared(&self) -> Option<Predicate<T>> {
+        let not = |p: &Predicate<T>| Predicate::Not(Box::new(p.clone()));
+        let and = |p1: Predicate<T>, p2: Predicate<T>| Predicate::And(Box::new(p1), Box::new(p2));
+        let or = |p1: Predicate<T>, p2: Predicate<T>| Predicate::Or(Box::new(p1), Box::new(p2));
+
+        match self {
+            Predicate::Implies(p1, p2) => Some(or(not(p1), *p2.clone())),
+            Predicate::Iff(p1, p2) => Some(or(and(*p1.clone(), *p2.clone()), and(not(p1), not(p2)))),
+            Predicate::Xor(p1, p2) => Some(or(and(*p1.clone(), not(p2)), and(not(p1), *p2.clone()))),
+            _ => None
+        }
+    }
+
+
+    /// Return an equivalent predicate in negation normal form, where negations are only applied to
+    /// boolean arguments and equalities (the negation of any other comparison being a comparison).
+    /// Implications, equivalences and exclusive disjunctions are written with conjunctions and disjunctions.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let p = Predicate::from("!((x > 5) || (y < 2))").unwrap();
+    /// assert_eq!(p.to_nnf(), Predicate::from("(x <= 5) && (y >= 2)").unwrap());
+    /// ```
+    pub fn to_nnf(&self) -> Predicate<T> {
+        self.nnf(false)
+    }
+
+
+    /// Return the negation normal form of the predicate, or of its negation.
+    fn nnf(&self, negated: bool) -> Predicate<T> {
+        match (self, negated) {
+            (Predicate::Not(p), _) => p.nnf(!negated),
+
+            (Predicate::And(p1, p2), false) => Predicate::And(Box::new(p1.nnf(false)), Box::new(p2.nnf(false))),
+            (Predicate::Or(p1, p2), false) => Predicate::Or(Box::new(p1.nnf(false)), Box::new(p2.nnf(false))),
+            (Predicate::And(p1, p2), true) => Predicate::Or(Box::new(p1.nnf(true)), Box::new(p2.nnf(true))),
+            (Predicate::Or(p1, p2), true) => Predicate::And(Box::new(p1.nnf(true)), Box::new(p2.nnf(true))),
+
+            // the negation of a <=> b is a xor b
+            (Predicate::Iff(p1, p2), true) => Predicate::Xor(p1.clone(), p2.clone()).nnf(false),
+            (Predicate::Xor(p1, p2), true) => Predicate::Iff(p1.clone(), p2.clone()).nnf(false),
+            (Predicate::Implies(..) | Predicate::Iff(..) | Predicate::Xor(..), _) => self.desugared().unwrap().nnf(negated),
+
+            (_, false) => self.clone(),
+
+            (Predicate::True, true) => Predicate::False,
+            (Predicate::False, true) => Predicate::True,
+            (Predicate::LowerThan(v1, v2), true) => Predicate::GreaterEqual(v1.clone(), v2.clone()),
+            (Predicate::LowerEqual(v1, v2), true) => Predicate::GreaterThan(v1.clone(), v2.clone()),
+            (Predicate::GreaterThan(v1, v2), true) => Predicate::LowerEqual(v1.clone(), v2.clone()),
+            (Predicate::GreaterEqual(v1, v2), true) => Predicate::LowerThan(v1.clone(), v2.clone()),
+            (Predicate::In(v, set), true) => Predicate::NotIn(v.clone(), set.clone()),
+            (Predicate::NotIn(v, set), true) => Predicate::In(v.clone(), set.clone()),
+
+            // boolean arguments and equalities
+            (_, true) => Predicate::Not(Box::new(self.clone()))
+        }
+    }
+
+
+
+
+    /// Return an equivalent predicate in disjunctive normal form: a disjunction of conjunctions
+    /// of comparisons, boolean arguments and their negations.
+    ///
+    /// The size of the result can grow exponentially with the size of the predicate.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let p = Predicate::from("(a || b) && (x > 5)").unwrap();
+    /// assert_eq!(p.to_dnf(), Predicate::from("(a && (x > 5)) || (b && (x > 5))").unwrap());
+    /// ```
+    pub fn to_dnf(&self) -> Predicate<T> {
+        disjunction(self.to_nnf().dnf_terms().into_iter().map(conjunction).collect())
+    }
+
+
+    /// Return an equivalent predicate in conjunctive normal form: a conjunction of disjunctions
+    /// of comparisons, boolean arguments and their negations.
+    ///
+    /// The size of the result can grow exponentially with the size of the predicate.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let p = Predicate::from("(a && b) || (x > 5)").unwrap();
+    /// assert_eq!(p.to_cnf(), Predicate::from("(a || (x > 5)) && (b || (x > 5))").unwrap());
+    /// ```
+    pub fn to_cnf(&self) -> Predicate<T> {
+        conjunction(self.to_nnf().cnf_clauses().into_iter().map(disjunction).collect())
+    }
+
+
+    /// Return the conjunctions whose disjunction is equivalent to the predicate, which must be in negation normal form.
+    fn dnf_terms(&self) -> Vec<Vec<Predicate<T>>> {
+        match self {
+            Predicate::True => vec![vec![]],
+            Predicate::False => vec![],
+
+            Predicate::Or(p1, p2) => {
+                let mut res = p1.dnf_terms();
+                res.extend(p2.dnf_terms());
+                res
+            },
+
+            Predicate::And(p1, p2) => {
+                let terms = p2.dnf_terms();
+                p1.dnf_terms().into_iter().flat_map(|t1| terms.iter().map(move |t2| {
+                    let mut t = t1.clone();
+                    t.extend(t2.iter().cloned());
+                    t
+                })).collect()
+            },
+
+            _ => vec![vec![self.clone()]]
+        }
+    }
+
+
+    /// Return the disjunctions whose conjunction is equivalent to the predicate, which must be in negation normal form.
+    fn cnf_clauses(&self) -> Vec<Vec<Predicate<T>>> {
+        match self {
+            Predicate::True => vec![],
+            Predicate::False => vec![vec![]],
+
+            Predicate::And(p1, p2) => {
+                let mut res = p1.cnf_clauses();
+                res.extend(p2.cnf_clauses());
+                res
+            },
+
+            Predicate::Or(p1, p2) => {
+                let clauses = p2.cnf_clauses();
+                p1.cnf_clauses().into_iter().flat_map(|c1| clauses.iter().map(move |c2| {
+                    let mut c = c1.clone();
+                    c.extend(c2.iter().cloned());
+                    c
+                })).collect()
+            },
+
+            _ => vec![vec![self.clone()]]
+        }
+    }
+}
+
+
+
+
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> Predicate<T> {
+    /// Return an equivalent predicate, simplified by:
+    /// - replacing comparisons that do not depend on any argument by their truth value (`5 > 4` becomes `True`),
+    /// - removing double negations,
+    /// - removing `True` and `False` from conjunctions and disjunctions, or replacing them by it,
+    /// - removing duplicated operands of conjunctions and disjunctions, and detecting operands that are negations of each other,
+    /// - merging the comparisons of a conjunction or disjunction that depend on the same single argument.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let p = Predicate::from("(x > 5) && (y < 2) && (x >= 7) && (5 > 4)").unwrap();
+    /// assert_eq!(p.simplify(), Predicate::from("(x >= 7) && (y < 2)").unwrap());
+    ///
+    /// let p = Predicate::from("(x < 1) || a || (x < 3) || (2 > 3)").unwrap();
+    /// assert_eq!(p.simplify(), Predicate::from("(x < 3) || a").unwrap());
+    /// ```
+    pub fn simplify(&self) -> Predicate<T> {
+        match self {
+            Predicate::True | Predicate::False | Predicate::BoolArg(_) => self.clone(),
+
+            Predicate::Not(p) => match p.simplify() {
+                Predicate::Not(q) => *q,
+                q @ (Predicate::BoolArg(_) | Predicate::Equal(..) | Predicate::And(..) | Predicate::Or(..)) => Predicate::Not(Box::new(q)),
+                q => q.nnf(true)     // True, False and comparisons other than equalities have a direct negation
+            },
+
+            Predicate::And(..) | Predicate::Or(..) => {
+                let is_and = matches!(self, Predicate::And(..));
+                let operands = self.operands(is_and).into_iter().flat_map(|p| p.simplify().operands(is_and)).collect();
+                Predicate::combine(operands, is_and)
+            },
+
+            Predicate::Implies(p1, p2) | Predicate::Iff(p1, p2) | Predicate::Xor(p1, p2) => {
+                let (p1, p2) = (Box::new(p1.simplify()), Box::new(p2.simplify()));
+
+                // with a constant operand, the predicate is the other operand, its negation or a constant
+                if [&p1, &p2].iter().any(|p| matches!(***p, Predicate::True | Predicate::False)) {
+                    return self.desugared().unwrap().simplify()
+                }
+
+                match self {
+                    Predicate::Implies(..) => Predicate::Implies(p1, p2),
+                    Predicate::Iff(..) => Predicate::Iff(p1, p2),
+                    _ => Predicate::Xor(p1, p2)
+                }
+            },
+
+            // comparisons
+            _ => match self.get_constraints(false) {
+                Some(cases) if cases.iter().flatten().all(|c| c.evaluate().is_some()) => {
+                    if cases.iter().any(|case| case.iter().all(|c| c.evaluate() == Some(true))) {Predicate::True}
+                    else {Predicate::False}
+                },
+                _ => self.clone()
+            }
+        }
+    }
+
+
+    /// Return the operands of the conjunction (if `is_and`) or disjunction formed by the predicate.
+    /// Nested conjunctions (or disjunctions) are flattened, and a predicate which is not one is its own only operand.
+    fn operands(&self, is_and: bool) -> Vec<Predicate<T>> {
+        match self {
+            Predicate::And(p1, p2) if is_and => {
+                let mut res = p1.operands(is_and);
+                res.extend(p2.operands(is_and));
+                res
+            },
+            Predicate::Or(p1, p2) if !is_and => {
+                let mut res = p1.operands(is_and);
+                res.extend(p2.operands(is_and));
+                res
+            },
+            _ => vec![self.clone()]
+        }
+    }
+
+
+    /// Return the argument of a linear comparison (or of its negation) which depends on a single argument.
+    fn single_argument(&self) -> Option<String> {
+        match self {
+            Predicate::Not(p) => p.single_argument(),
+            _ => {
+                self.get_constraints(false)?;
+                let args = self.get_arguments();
+                if args.len() == 1 {args.into_iter().next()} else {None}
+            }
+        }
+    }
+
+
+    /// Return the conjunction (if `is_and`) or disjunction of simplified operands, after simplifying it further.
+    fn combine(mut operands: Vec<Predicate<T>>, is_and: bool) -> Predicate<T> {
+        let (neutral, absorbing) = if is_and {(Predicate::True, Predicate::False)} else {(Predicate::False, Predicate::True)};
+
+        // merge the comparisons depending on the same argument into the domain of this argument
+        let mut groups: Vec<(String, Vec<usize>)> = vec![];
+        for (i, p) in operands.iter().enumerate() {
+            if let Some(a) = p.single_argument() {
+                match groups.iter_mut().find(|(b, _)| *b == a) {
+                    Some((_, indices)) => indices.push(i),
+                    None => groups.push((a, vec![i]))
+                }
+            }
+        }
+
+        for (a, indices) in groups.into_iter().filter(|(_, indices)| indices.len() > 1) {
+            let domain = indices.iter()
+                .map(|i| operands[*i].get_domain(&a))
+                .reduce(|d1, d2| if is_and {Domain::intersection(d1, d2)} else {Domain::union(d1, d2)})
+                .unwrap();
+
+            // the merged comparisons take the place of the first one
+            operands[indices[0]] = domain.to_predicate(&a);
+            for i in &indices[1..] {operands[*i] = neutral.clone();}
+        }
+
+        let mut res: Vec<Predicate<T>> = vec![];
+        for p in operands.into_iter().flat_map(|p| p.operands(is_and)) {
+            if p == absorbing {return absorbing}
+            if p == neutral || res.contains(&p) {continue}
+
+            // p && !p is always false, p || !p always true
+            let negated = Predicate::Not(Box::new(p.clone()));
+            if res.iter().any(|q| *q == negated || Predicate::Not(Box::new(q.clone())) == p) {return absorbing}
+
+            res.push(p);
+        }
+
+        if is_and {conjunction(res)} else {disjunction(res)}
+    }
+}
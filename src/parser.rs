@@ -1,4 +1,4 @@
-///! Small parser to convert a string into a predicate.
+//! Small parser to convert a string into a predicate.
 
 
 
@@ -6,91 +6,372 @@
 // A better parser would be the one used in Sloth, which is more general and more robust.
 // cf. https://github.com/MyselfLeo/sloth
 
-use crate::{Predicate, Value};
+use std::str::FromStr;
 
-const VALUE_OPS: [&str; 5] = ["==", ">", "<", ">=", "<="];
-const PREDICATE_OPS: [&str; 3] = ["||", "&&", "!"];
+use num::Num;
 
-const OPERATORS: [&str; 8] = ["==", ">", "<", ">=", "<=", "||", "&&", "!"];
-const SEPARATORS: [&str; 2] = ["(", ")"];
+use crate::{Predicate, Value};
+use crate::domain::is_nan;
+use crate::error::{ParseError, Span};
+use crate::schema::Schema;
+
+const ARITHMETIC_OPS: [&str; 3] = ["+", "-", "*"];
+const VALUE_OPS: [&str; 8] = ["==", "!=", ">", "<", ">=", "<=", "in", "not in"];
+const PREDICATE_OPS: [&str; 5] = ["||", "&&", "xor", "=>", "<=>"];
+
+// operators that can be written before an operand, applying to it only
+const UNARY_OPS: [&str; 3] = ["-", "!", "not"];
+
+// operators written with symbols or keywords, and the operator they are
+const SYMBOL_OPS: [(&str, &str); 14] = [
+    ("+", "+"), ("-", "-"), ("*", "*"), ("==", "=="), ("!=", "!="), (">", ">"), ("<", "<"),
+    (">=", ">="), ("<=", "<="), ("||", "||"), ("&&", "&&"), ("!", "!"), ("=>", "=>"), ("<=>", "<=>")
+];
+const UNICODE_OPS: [(&str, &str); 9] = [
+    ("≤", "<="), ("≥", ">="), ("≠", "!="), ("∧", "&&"), ("∨", "||"), ("¬", "!"), ("⇒", "=>"), ("⇔", "<=>"), ("⊕", "xor")
+];
+const KEYWORD_OPS: [(&str, &str); 5] = [("and", "&&"), ("or", "||"), ("not", "not"), ("in", "in"), ("xor", "xor")];
+const SEPARATORS: [&str; 7] = ["(", ")", "[", "]", "{", "}", ","];
+
+// characters of the operators, used to detect unknown ones
+const OPERATOR_CHARS: &str = "=<>!&|+-*/%^~";
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum Token {
+pub enum Token<T> {
     Boolean(bool),
     Operator(String),
+    Unary(String),
     Separator(String),
     Arg(String),
-    Literal(f64)
+    Literal(T),
+
+    /// Interval like `[0, 10)`, with its bounds and whether they are included
+    Interval(T, bool, T, bool),
+
+    /// Set of values like `{1, 2, 5}`
+    Set(Vec<T>)
+}
+
+
+
+/// Spelling of the equality in the infix notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// The equality is written `==`, `=` being an unknown operator
+    #[default]
+    DoubleEquals,
+
+    /// The equality is written `=` (as in SQL or in mathematics), or `==`
+    SingleEquals
+}
+
+
+
+/// Return true if the character can be part of a word (an argument, a keyword or a literal).
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+
+/// Return the byte offset following the word starting at `start`. The exponent of a number (like `1e-3`)
+/// and the denominator of a fraction (like `1/3`) are part of it.
+fn word_end(txt: &str, start: usize) -> usize {
+    let number = txt[start..].starts_with(|c: char| c.is_ascii_digit() || c == '.');
+    let mut previous = None;
+
+    for (i, c) in txt[start..].char_indices() {
+        let exponent = number && (c == '+' || c == '-') && matches!(previous, Some('e' | 'E'));
+        let fraction = number && c == '/' && txt[start + i + 1..].starts_with(|c: char| c.is_ascii_digit());
+        if !is_word_char(c) && !exponent && !fraction {return start + i}
+        previous = Some(c);
+    }
+    txt.len()
+}
+
+
+/// Return the operator written with symbols at the start of the string, with its length in bytes.
+fn symbol_operator(txt: &str, dialect: Dialect) -> Option<(&'static str, usize)> {
+    let equal: &[(&str, &str)] = match dialect {
+        Dialect::DoubleEquals => &[],
+        Dialect::SingleEquals => &[("=", "==")]
+    };
+
+    // the longest operators are tried first, so that `<=` is not read as `<`
+    SYMBOL_OPS.iter().chain(UNICODE_OPS.iter()).chain(equal)
+        .filter(|(symbol, _)| txt.starts_with(symbol))
+        .max_by_key(|(symbol, _)| symbol.len())
+        .map(|(symbol, op)| (*op, symbol.len()))
+}
+
+
+
+/// Convert a string into a Vec of tokens, each with its location in the string.
+/// Literals are read using the [FromStr] implementation of their type, or as decimal numbers (see [parse_decimal]).
+///
+/// Tokens do not need to be separated by spaces (`x>5` is `x > 5`). A `-` directly followed by a number is
+/// part of it where an operand is expected, so that `- 5` is the negation of the literal `5` but `-5` is a literal.
+pub fn parse<T: Num + Clone + FromStr>(txt: &str, dialect: Dialect) -> Result<Vec<(Token<T>, Span)>, ParseError> {
+    let mut res: Vec<(Token<T>, Span)> = vec![];
+    let mut i = 0;
+
+    while let Some(c) = txt[i..].chars().next() {
+        let start = i;
+        let expect_operand = match res.last() {
+            None | Some((Token::Operator(_), _)) => true,
+            Some((Token::Separator(s), _)) => s == "(" || s == "[" || s == "{" || s == ",",
+            _ => false
+        };
+
+        let token = if c.is_whitespace() {
+            i += c.len_utf8();
+            continue
+        }
+
+        else if SEPARATORS.contains(&c.to_string().as_str()) {
+            i += 1;
+            Token::Separator(c.to_string())
+        }
+
+        // negative literal
+        else if let Some(x) = (c == '-' && expect_operand).then(|| parse_literal(&txt[start..word_end(txt, start + 1)])).flatten() {
+            i = word_end(txt, start + 1);
+            Token::Literal(x)
+        }
+
+        else if let Some((op, len)) = symbol_operator(&txt[start..], dialect) {
+            i += len;
+            Token::Operator(op.to_string())
+        }
+
+        else if OPERATOR_CHARS.contains(c) {
+            let end = txt[start..].find(|c| !OPERATOR_CHARS.contains(c)).map_or(txt.len(), |e| start + e);
+            return Err(ParseError::UnknownOperator(txt[start..end].to_string(), Span::new(txt, start, end)))
+        }
+
+        else if is_word_char(c) {
+            i = word_end(txt, start);
+            parse_word(&txt[start..i]).ok_or_else(|| ParseError::UnexpectedToken(txt[start..i].to_string(), Span::new(txt, start, i)))?
+        }
+
+        else {
+            i += c.len_utf8();
+            return Err(ParseError::UnexpectedToken(c.to_string(), Span::new(txt, start, i)))
+        };
+
+        res.push((token, Span::new(txt, start, i)));
+    }
+
+    read_collections(txt, res)
+}
+
+
+
+/// Replace the tokens of the operands of the `in` and `not in` operators by a [Token::Interval] (like `[0, 10)`, a bracket
+/// being the one of an included bound and a parenthesis the one of an excluded bound) or a [Token::Set] (like `{1, 2, 5}`).
+fn read_collections<T>(txt: &str, tokens: Vec<(Token<T>, Span)>) -> Result<Vec<(Token<T>, Span)>, ParseError> {
+    let mut res = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some((t, span)) = tokens.next() {
+        // `not in` is a single operator, `not` being unary otherwise
+        let (t, span) = match t {
+            Token::Operator(op) if op == "not" => match tokens.next_if(|(t, _)| matches!(t, Token::Operator(op) if op == "in")) {
+                Some((_, end)) => (Token::Operator("not in".to_string()), join(&span, &end)),
+                None => (Token::Operator(op), span)
+            },
+            t => (t, span)
+        };
+
+        let is_in = matches!(&t, Token::Operator(op) if op == "in" || op == "not in");
+        res.push((t, span));
+        if !is_in {continue}
+
+        let (open, start) = match tokens.next_if(|(t, _)| matches!(t, Token::Separator(s) if s == "[" || s == "(" || s == "{")) {
+            Some((Token::Separator(s), span)) => (s, span),
+            _ => continue
+        };
+
+        // read a literal (if `literal` is true) or one of the given separators
+        let mut next = |literal: bool, separators: &str| match tokens.next() {
+            Some((t, span)) => match t {
+                Token::Literal(x) if literal => Ok((Some(x), String::new(), span)),
+                Token::Separator(s) if separators.contains(s.as_str()) => Ok((None, s, span)),
+                _ => Err(ParseError::UnexpectedToken(txt[span.start..span.end].to_string(), span))
+            },
+            None => Err(ParseError::UnbalancedParenthesis(start))
+        };
+
+        if open == "{" {
+            let mut set = vec![];
+            let (mut x, mut s, mut end) = next(true, "}")?;
+
+            // the elements are separated by commas
+            while s.is_empty() {
+                set.push(x.take().unwrap());
+                (_, s, end) = next(false, ",}")?;
+                if s == "," {(x, s, _) = next(true, "")?;}
+            }
+
+            res.push((Token::Set(set), join(&start, &end)));
+            continue
+        }
+
+        let (lower, _, _) = next(true, "")?;
+        next(false, ",")?;
+        let (greater, _, _) = next(true, "")?;
+        let (_, close, end) = next(false, "])")?;
+
+        res.push((Token::Interval(lower.unwrap(), open == "[", greater.unwrap(), close == "]"), join(&start, &end)));
+    }
+
+    Ok(res)
+}
+
+
+
+/// Read a decimal number like `-12.75` as the exact value `-1275 / 100`, for the types which [FromStr]
+/// implementation does not read them (like `BigRational`). Return None if the value can not be represented exactly.
+fn parse_decimal<T: Num + Clone + FromStr>(t: &str) -> Option<T> {
+    let (negative, t) = match t.strip_prefix('-') {
+        Some(t) => (true, t),
+        None => (false, t)
+    };
+
+    let (int, frac) = t.split_once('.')?;
+    if int.is_empty() || frac.is_empty() || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit()) {return None}
+
+    let digits = format!("{int}{frac}").parse::<T>().ok()?;
+    let ten = "10".parse::<T>().ok()?;
+    let scale = frac.chars().fold(T::one(), |x, _| x * ten.clone());
+
+    // with integer types, the division is not exact if the number has a fractional part
+    let x = digits.clone() / scale.clone();
+    if x.clone() * scale != digits {return None}
+
+    Some(if negative {T::zero() - x} else {x})
+}
+
+
+
+/// Read a literal of the type `T` using its [FromStr] implementation, or as a decimal number.
+pub fn parse_literal<T: Num + Clone + FromStr>(t: &str) -> Option<T> {
+    t.parse::<T>().ok().or_else(|| parse_decimal(t))
 }
 
 
 
-/// Convert a string into a Vec of tokens
-pub fn parse(txt: &str) -> Result<Vec<Token>, String> {
-    let txt_cleaned = txt.replace("(", " ( ").replace(")", " ) ");
-    txt_cleaned.split(' ').filter(|x| !x.trim().is_empty()).map( |t|
-        if t == "true" {Ok(Token::Boolean(true))}
-        else if t == "false" {Ok(Token::Boolean(false))}
-        else if OPERATORS.contains(&t) {Ok(Token::Operator(t.to_string()))}
-        else if SEPARATORS.contains(&t) {Ok(Token::Separator(t.to_string()))}
-        else if t.parse::<f64>().is_ok() {Ok(Token::Literal(t.parse::<f64>().unwrap()))}
-        else if t.chars().next().unwrap().is_alphabetic() {Ok(Token::Arg(t.to_string()))}
-        else {Err(format!("Invalid token: {}", t))}
-    ).collect()
+/// Convert a word into a token: a keyword, a literal or an argument.
+/// Return None if it is none of them (like `2x`).
+fn parse_word<T: Num + Clone + FromStr>(t: &str) -> Option<Token<T>> {
+    if t == "true" {Some(Token::Boolean(true))}
+    else if t == "false" {Some(Token::Boolean(false))}
+    else if let Some((_, op)) = KEYWORD_OPS.iter().find(|(k, _)| *k == t) {Some(Token::Operator(op.to_string()))}
+    else if let Some(x) = parse_literal(t) {Some(Token::Literal(x))}
+    else if t.starts_with(char::is_alphabetic) {Some(Token::Arg(t.to_string()))}
+    else {None}
 }
 
 
 
 
+/// Return the precedence of a binary operator: operators with a higher precedence are applied first.
+/// Arithmetic operators have a higher precedence than value operators (<, ==, etc.),
+/// which have a higher precedence than boolean operators (&&, ||, etc.), `=>` and `<=>` being applied last.
+fn precedence(op: &str) -> u8 {
+    match op {
+        "*" => 5,
+        "+" | "-" => 4,
+        _ if VALUE_OPS.contains(&op) => 3,
+        "=>" | "<=>" => 0,
+        _ => 1
+    }
+}
+
+/// Return the precedence of a unary operator: `-` is applied first, and `!` after the comparisons
+/// but before the boolean operators (`!x > 5 && a` is `!(x > 5) && a`).
+fn unary_precedence(op: &str) -> u8 {
+    if op == "-" {6} else {2}
+}
+
+
+
 /// Convert an infix vec of tokens into a postfix stream one
-/// This function uses the Shunting-Yard algorithm
-pub fn infix_to_postfix(tokens: Vec<Token>) -> Result<Vec<Token>, String> {
+/// This function uses the Shunting-Yard algorithm, and checks that operands and operators alternate correctly.
+/// The closing parentheses are kept in the stream, as they end the chains of comparisons.
+pub fn infix_to_postfix<T>(txt: &str, tokens: Vec<(Token<T>, Span)>) -> Result<Vec<(Token<T>, Span)>, ParseError> {
     let mut res = vec![];
-    let mut operator_stack = vec![];
+    let mut operator_stack: Vec<(Token<T>, Span)> = vec![];
+    let mut expect_operand = true;
 
-    for t in tokens {
+    for (t, span) in tokens {
         match &t {
-            Token::Operator(x) => {
+            Token::Operator(x) if expect_operand => {
+                if !UNARY_OPS.contains(&x.as_str()) {return Err(ParseError::MissingOperand(x.clone(), span))}
+                operator_stack.push((Token::Unary(x.clone()), span));
+            },
 
-                if VALUE_OPS.contains(&x.as_str()) {    // Value operators (<, ==, etc.) have a higher precidence than boolean operators (&&, ||, etc.)
-                    while let Some(Token::Operator(x)) = operator_stack.last() {
-                        if !VALUE_OPS.contains(&x.as_str()) {break;}
-                        else {
-                            res.push(operator_stack.pop().unwrap());
-                        }
+            Token::Operator(x) => {
+                // `!` and `not` are only unary operators
+                if x == "!" || x == "not" {return Err(ParseError::UnexpectedToken(x.clone(), span))}
+
+                // every binary operator is left-associative, except `=>` (`a => b => c` is `a => (b => c)`)
+                loop {
+                    match operator_stack.last() {
+                        Some((Token::Unary(y), _)) if unary_precedence(y) >= precedence(x) => (),
+                        Some((Token::Operator(y), _)) if precedence(y) > precedence(x) => (),
+                        Some((Token::Operator(y), _)) if precedence(y) == precedence(x) && x != "=>" => (),
+                        _ => break
                     }
+                    res.push(operator_stack.pop().unwrap());
                 }
 
-                else if PREDICATE_OPS.contains(&x.as_str()) {
-                    while let Some(Token::Operator(_)) = operator_stack.last() {
-                        res.push(operator_stack.pop().unwrap());
-                    }
-                }
+                operator_stack.push((t, span));
+                expect_operand = true;
+            },
 
-                operator_stack.push(t);
+            Token::Separator(s) if s == "(" => {
+                if !expect_operand {return Err(ParseError::UnexpectedToken(s.clone(), span))}
+                operator_stack.push((t, span));
             },
 
-            Token::Separator(s) => {
-                if s == "(" {
-                    operator_stack.push(t);
-                }
-                else if s == ")" {
-                    while *operator_stack.last().unwrap() != Token::Separator("(".to_string()) {
-                        res.push(operator_stack.pop().unwrap())
+            Token::Separator(s) if s == ")" => {
+                if expect_operand {return Err(ParseError::UnexpectedToken(s.clone(), span))}
+
+                loop {
+                    match operator_stack.pop() {
+                        Some((Token::Separator(_), _)) => break,
+                        Some(op) => res.push(op),
+                        None => return Err(ParseError::UnbalancedParenthesis(span))
                     }
-                    operator_stack.pop().unwrap();
                 }
-                else {return Err(format!("Invalid predicate string"))}
+                res.push((t, span));
             },
 
-            Token::Arg(_) => res.push(t),
-            Token::Literal(_) => res.push(t),
-            Token::Boolean(_) => res.push(t),
+            // brackets, braces and commas are only used by intervals and sets
+            Token::Separator(s) => return Err(ParseError::UnexpectedToken(s.clone(), span)),
+
+            Token::Unary(_) => unreachable!("unary operators are only identified here"),
+
+            Token::Arg(_) | Token::Literal(_) | Token::Boolean(_) | Token::Interval(..) | Token::Set(_) => {
+                if !expect_operand {return Err(ParseError::UnexpectedToken(txt[span.start..span.end].to_string(), span))}
+                res.push((t, span));
+                expect_operand = false;
+            }
+        }
+    }
+
+    // the string is empty, or ends with an operator or an opening parenthesis
+    if expect_operand {
+        return match operator_stack.pop() {
+            Some((Token::Operator(op) | Token::Unary(op), span)) => Err(ParseError::MissingOperand(op, span)),
+            Some((_, span)) => Err(ParseError::UnbalancedParenthesis(span)),
+            None => Err(ParseError::UnexpectedToken(String::new(), Span::new(txt, txt.len(), txt.len())))
         }
-    }    
+    }
 
-    while !operator_stack.is_empty() {
-        res.push(operator_stack.pop().unwrap())
+    while let Some((t, span)) = operator_stack.pop() {
+        if let Token::Separator(_) = t {return Err(ParseError::UnbalancedParenthesis(span))}
+        res.push((t, span));
     }
 
     Ok(res)
@@ -98,66 +379,243 @@ pub fn infix_to_postfix(tokens: Vec<Token>) -> Result<Vec<Token>, String> {
 
 
 
+/// Return the span covering two spans, the first one being before the second.
+fn join(s1: &Span, s2: &Span) -> Span {
+    Span { start: s1.start, end: s2.end, line: s1.line, column: s1.column }
+}
+
+
+
+/// Operand of an operator, either a value or a predicate
+enum Operand<T: Num + PartialOrd> {
+    Value(Value<T>),
+    Predicate(Predicate<T>),
+
+    /// Comparison with its right value, which is compared again if the comparison is chained (like `0 <= x < 10`)
+    Comparison(Predicate<T>, Value<T>),
+
+    /// Interval following the `in` operator
+    Interval(T, bool, T, bool),
+
+    /// Set following the `in` operator
+    Set(Vec<T>)
+}
+
+
+
+impl<T: Num + PartialOrd> Operand<T> {
+    /// Return the operand as a value, if it is one
+    fn into_value(self) -> Option<Value<T>> {
+        match self {
+            Operand::Value(v) => Some(v),
+            _ => None
+        }
+    }
+
+    /// Return the operand as a predicate, if it is one.
+    /// An argument used where a predicate is expected is a boolean argument.
+    fn into_predicate(self) -> Option<Predicate<T>> {
+        match self {
+            Operand::Predicate(p) | Operand::Comparison(p, _) => Some(p),
+            Operand::Value(Value::Arg(a)) => Some(Predicate::BoolArg(a)),
+            _ => None
+        }
+    }
+
+    /// Check that the operand can be used as a predicate (or as a value) if it is an argument declared by the schema.
+    fn check(&self, as_predicate: bool, span: &Span, schema: Option<&Schema<T>>) -> Result<(), ParseError> {
+        if let (Operand::Value(Value::Arg(a)), Some(schema)) = (self, schema) {
+            if schema.get_type(a).is_some_and(|t| t.is_bool() != as_predicate) {
+                return Err(ParseError::WrongType(a.clone(), *span))
+            }
+        }
+        Ok(())
+    }
+}
+
+
+
 /// Create a predicate from a infix string for example `(x > 5) && (x < 10)
-pub fn parse_predicate(txt: &str) -> Result<Predicate<f64>, String> {
-    let tokens = infix_to_postfix(parse(txt)?)?;
+/// If a schema is given, the arguments must be declared by it and used according to their type.
+pub fn parse_predicate<T: Num + PartialOrd + Clone + FromStr>(txt: &str, schema: Option<&Schema<T>>, dialect: Dialect) -> Result<Predicate<T>, ParseError> {
+    let tokens = infix_to_postfix(txt, parse(txt, dialect)?)?;
 
-    let mut predicate_stack = vec![];
-    let mut value_stack = vec![];
+    let mut stack: Vec<(Operand<T>, Span)> = vec![];
 
-    for token in tokens {
+    for (token, span) in tokens {
 
         match token {
 
-            Token::Boolean(true) => predicate_stack.push(Predicate::True),
-            Token::Boolean(false) => predicate_stack.push(Predicate::False),
-            Token::Arg(x) => value_stack.push(Value::Arg(x)),
-            Token::Literal(l) => value_stack.push(Value::Literal(l)),
+            Token::Boolean(true) => stack.push((Operand::Predicate(Predicate::True), span)),
+            Token::Boolean(false) => stack.push((Operand::Predicate(Predicate::False), span)),
+            Token::Arg(x) => {
+                if schema.is_some_and(|s| s.get_type(&x).is_none()) {return Err(ParseError::UndeclaredArgument(x, span))}
+                stack.push((Operand::Value(Value::Arg(x)), span))
+            },
+            Token::Literal(l) => {
+                if is_nan(&l) {return Err(ParseError::NotANumber(span))}
+                stack.push((Operand::Value(Value::Literal(l)), span))
+            },
+            Token::Interval(l, incl_l, g, incl_g) => {
+                if is_nan(&l) || is_nan(&g) {return Err(ParseError::NotANumber(span))}
+                stack.push((Operand::Interval(l, incl_l, g, incl_g), span))
+            },
+            Token::Set(set) => {
+                if set.iter().any(is_nan) {return Err(ParseError::NotANumber(span))}
+                stack.push((Operand::Set(set), span))
+            },
+
+
+            Token::Unary(op) => {
+                let (o, s) = match stack.pop() {
+                    Some(x) => x,
+                    None => return Err(ParseError::MissingOperand(op, span))
+                };
+                let op_span = span;
+                let span = join(&op_span, &s);
+                o.check(op != "-", &s, schema)?;
+
+                let o = match op.as_str() {
+                    "-" => o.into_value().map(|v| Operand::Value(Value::Neg(Box::new(v)))),
+                    "!" | "not" => o.into_predicate().map(|p| Operand::Predicate(Predicate::Not(Box::new(p)))),
+                    _ => return Err(ParseError::UnknownOperator(op, op_span))
+                };
+
+                match o {
+                    Some(o) => stack.push((o, span)),
+                    None => return Err(ParseError::MissingOperand(op, op_span))
+                }
+            },
 
 
             Token::Operator(op) => {
-                if VALUE_OPS.contains(&op.as_str()) {
-                    if value_stack.len() < 2 {return Err(format!("Invalid predicate string"))}
-
-                    let v2 = value_stack.pop().unwrap();
-                    let v1 = value_stack.pop().unwrap();
-
-                    match op.as_str() {
-                        //"==", ">", "<", ">=", "<="
-                        "==" => predicate_stack.push(Predicate::Equal(v1, v2)),
-                        ">" => predicate_stack.push(Predicate::GreaterThan(v1, v2)),
-                        "<" => predicate_stack.push(Predicate::LowerThan(v1, v2)),
-                        ">=" => predicate_stack.push(Predicate::GreaterEqual(v1, v2)),
-                        "<=" => predicate_stack.push(Predicate::LowerEqual(v1, v2)),
-                        _ => return Err(format!("Invalid predicate string"))
-                    }
+                if stack.len() < 2 {return Err(ParseError::MissingOperand(op, span))}
+
+                let (o2, s2) = stack.pop().unwrap();
+                let (o1, s1) = stack.pop().unwrap();
+                let op_span = span;
+                let span = join(&s1, &s2);
+
+                let predicate_op = PREDICATE_OPS.contains(&op.as_str());
+                o1.check(predicate_op, &s1, schema)?;
+                o2.check(predicate_op, &s2, schema)?;
+
+                if ARITHMETIC_OPS.contains(&op.as_str()) {
+                    let (v1, v2) = match (o1.into_value(), o2.into_value()) {
+                        (Some(v1), Some(v2)) => (v1, v2),
+                        _ => return Err(ParseError::MissingOperand(op, op_span))
+                    };
+
+                    let v = match op.as_str() {
+                        "+" => Value::Add(Box::new(v1), Box::new(v2)),
+                        "-" => Value::Sub(Box::new(v1), Box::new(v2)),
+                        "*" => {
+                            if !Value::is_linear_product([&v1, &v2]) {
+                                return Err(ParseError::NonLinearProduct(span))
+                            }
+                            Value::Mul(Box::new(v1), Box::new(v2))
+                        },
+                        _ => return Err(ParseError::UnknownOperator(op, op_span))
+                    };
+                    stack.push((Operand::Value(v), span));
                 }
 
-                else if PREDICATE_OPS.contains(&op.as_str()) {
-                    if predicate_stack.len() < 2 {return Err(format!("Invalid predicate string"))}
+                else if op == "in" || op == "not in" {
+                    let p = match (o1.into_value(), o2) {
+                        (Some(v), Operand::Set(set)) if op == "in" => Predicate::In(v, set),
+                        (Some(v), Operand::Set(set)) => Predicate::NotIn(v, set),
+                        (Some(v), Operand::Interval(l, incl_l, g, incl_g)) => {
+                            let lower = if incl_l {Predicate::GreaterEqual(v.clone(), Value::Literal(l))}
+                                else {Predicate::GreaterThan(v.clone(), Value::Literal(l))};
+                            let greater = if incl_g {Predicate::LowerEqual(v, Value::Literal(g))}
+                                else {Predicate::LowerThan(v, Value::Literal(g))};
+                            let p = Predicate::And(Box::new(lower), Box::new(greater));
+                            if op == "in" {p} else {Predicate::Not(Box::new(p))}
+                        },
+                        _ => return Err(ParseError::MissingOperand(op, op_span))
+                    };
+                    stack.push((Operand::Predicate(p), span));
+                }
 
-                    let p2 = predicate_stack.pop().unwrap();
-                    let p1 = predicate_stack.pop().unwrap();
+                else if VALUE_OPS.contains(&op.as_str()) {
+                    // the left operand of a chained comparison is the previous comparison, which right value is compared again
+                    let (previous, v1) = match o1 {
+                        Operand::Comparison(p, v) => (Some(p), Some(v)),
+                        o => (None, o.into_value())
+                    };
+                    let (v1, v2) = match (v1, o2.into_value()) {
+                        (Some(v1), Some(v2)) => (v1, v2),
+                        _ => return Err(ParseError::MissingOperand(op, op_span))
+                    };
+
+                    let right = v2.clone();
+                    let p = match op.as_str() {
+                        //"==", "!=", ">", "<", ">=", "<="
+                        "==" => Predicate::Equal(v1, v2),
+                        "!=" => Predicate::Not(Box::new(Predicate::Equal(v1, v2))),
+                        ">" => Predicate::GreaterThan(v1, v2),
+                        "<" => Predicate::LowerThan(v1, v2),
+                        ">=" => Predicate::GreaterEqual(v1, v2),
+                        "<=" => Predicate::LowerEqual(v1, v2),
+                        _ => return Err(ParseError::UnknownOperator(op, op_span))
+                    };
+
+                    let p = match previous {
+                        Some(previous) => Predicate::And(Box::new(previous), Box::new(p)),
+                        None => p
+                    };
+                    stack.push((Operand::Comparison(p, right), span));
+                }
 
-                    match op.as_str() {
-                        //"||", "&&", "!"
-                        "||" => predicate_stack.push(Predicate::Or(Box::new(p1), Box::new(p2))),
-                        "&&" => predicate_stack.push(Predicate::And(Box::new(p1), Box::new(p2))),
-                        "!" => predicate_stack.push(Predicate::Not(Box::new(p1))),
-                        _ => return Err(format!("Invalid predicate string"))
-                    }
+                else if PREDICATE_OPS.contains(&op.as_str()) {
+                    let (p1, p2) = match (o1.into_predicate(), o2.into_predicate()) {
+                        (Some(p1), Some(p2)) => (p1, p2),
+                        _ => return Err(ParseError::MissingOperand(op, op_span))
+                    };
+
+                    let p = match op.as_str() {
+                        //"||", "&&", "xor", "=>", "<=>"
+                        "||" => Predicate::Or(Box::new(p1), Box::new(p2)),
+                        "xor" => Predicate::Xor(Box::new(p1), Box::new(p2)),
+                        "=>" => Predicate::Implies(Box::new(p1), Box::new(p2)),
+                        "<=>" => Predicate::Iff(Box::new(p1), Box::new(p2)),
+                        "&&" => Predicate::And(Box::new(p1), Box::new(p2)),
+                        _ => return Err(ParseError::UnknownOperator(op, op_span))
+                    };
+                    stack.push((Operand::Predicate(p), span));
                 }
             },
 
 
-            Token::Separator(_) => panic!("Unexpected separator"),
+            // `(0 <= x) < 10` is not a chain of comparisons
+            Token::Separator(s) if s == ")" => {
+                if let Some((o, s)) = stack.pop() {
+                    let o = match o {
+                        Operand::Comparison(p, _) => Operand::Predicate(p),
+                        o => o
+                    };
+                    stack.push((o, s));
+                }
+            },
+
+            Token::Separator(s) => return Err(ParseError::UnexpectedToken(s, span)),
         }
 
     }
 
     // At this point there should be only one predicate in the stack
-    match predicate_stack.pop() {
-        Some(p) => Ok(p),
-        None => Err(format!("Invalid predicate string"))
+    if let Some((_, span)) = stack.get(1) {
+        return Err(ParseError::UnexpectedToken(txt[span.start..span.end].to_string(), *span))
+    }
+
+    match stack.pop() {
+        Some((o, span)) => {
+            o.check(true, &span, schema)?;
+            match o.into_predicate() {
+                Some(p) => Ok(p),
+                None => Err(ParseError::UnexpectedToken(txt[span.start..span.end].to_string(), span))
+            }
+        },
+        None => Err(ParseError::UnexpectedToken(String::new(), Span::new(txt, txt.len(), txt.len())))
     }
-}
\ No newline at end of file
+}
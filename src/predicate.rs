@@ -1,28 +1,34 @@
-use std::{fmt::{Display, Debug}, collections::HashSet};
+use std::{fmt::{Display, Debug}, collections::HashSet, str::FromStr};
 
-use num::{Num, ToPrimitive};
+use num::{Num, Signed, ToPrimitive};
 
-use crate::domain::Domain;
-use crate::parser::parse_predicate;
+use crate::arithmetic::Checked;
+use crate::assignment::{Assignment, Environment};
+use crate::domain::{Domain, is_nan};
+use crate::linear::{LinearExpr, Constraint};
+use crate::parser::{parse_predicate, Dialect};
+use crate::error::{ParseError, EvalError};
+use crate::schema::Schema;
+use crate::solver;
 
 
 /// Represent the "level" of an implication between two predicates A and B
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Implication {
     /// Any value that verifies A will verify B
     Total,
 
     /// only a subset of the values that verify A will verify B.
-    /// This happens because the predicate A uses an "or" operation for which only one operand verifies B.
-    Partial,
+    /// `coverage` is the proportion of the values verifying A that verify B (see [Predicate::coverage]), if it can be
+    /// measured and is neither 0 nor 1: the values verifying A that verify B, or the ones that do not, can be negligible.
+    Partial { coverage: Option<f64> },
 
-    /// The verification of A by a value x is not enough to 
-    /// know that x also verifies B
+    /// No value that verifies A will verify B
     Inexistant
 }
 
 
-/// In a [Predicate], a value can either be a literal or an argument.
+/// In a [Predicate], a value can either be a literal, an argument or an arithmetic expression of other values.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value<T: Num + PartialOrd> {
     /// Identifies by a string an argument which value is not known.
@@ -30,7 +36,104 @@ pub enum Value<T: Num + PartialOrd> {
     Arg(String),
 
     /// A known value, allowing for simplifications (for example, `5 > 4` is simplified to `True`)
-    Literal(T)
+    Literal(T),
+
+    Add(Box<Value<T>>, Box<Value<T>>),
+    Sub(Box<Value<T>>, Box<Value<T>>),
+    Neg(Box<Value<T>>),
+
+    /// Product of two values. The checker can only reason about linear expressions,
+    /// so one of the two values should not depend on any argument.
+    Mul(Box<Value<T>>, Box<Value<T>>)
+}
+
+
+
+impl<T: Num + PartialOrd + Clone> Value<T> {
+    /// Return the value obtained by replacing the arguments by their value in the environment.
+    /// With bounded integer types, an operation whose result can not be represented by `T` is an error.
+    pub fn evaluate(&self, env: &impl Environment<T>) -> Result<T, EvalError> where T: Display + Checked {
+        let overflow = || EvalError::Overflow(self.to_string());
+        match self {
+            Value::Arg(a) => match env.get_value(a) {
+                Some(x) => Ok(x),
+                None if env.get_bool(a).is_some() => Err(EvalError::WrongType(a.clone())),
+                None => Err(EvalError::Unbound(a.clone()))
+            },
+            Value::Literal(x) => Ok(x.clone()),
+            Value::Add(v1, v2) => v1.evaluate(env)?.checked_add(&v2.evaluate(env)?).ok_or_else(overflow),
+            Value::Sub(v1, v2) => v1.evaluate(env)?.checked_sub(&v2.evaluate(env)?).ok_or_else(overflow),
+            Value::Neg(v) => T::zero().checked_sub(&v.evaluate(env)?).ok_or_else(overflow),
+            Value::Mul(v1, v2) => v1.evaluate(env)?.checked_mul(&v2.evaluate(env)?).ok_or_else(overflow),
+        }
+    }
+
+
+    /// Return the set of arguments used by the value
+    pub fn get_arguments(&self) -> HashSet<String> {
+        match self {
+            Value::Arg(a) => {
+                let mut set = HashSet::new();
+                set.insert(a.clone());
+                set
+            },
+            Value::Literal(_) => HashSet::new(),
+            Value::Neg(v) => v.get_arguments(),
+            Value::Add(v1, v2) | Value::Sub(v1, v2) | Value::Mul(v1, v2) => {
+                let mut set = v1.get_arguments();
+                set.extend(v2.get_arguments());
+                set
+            }
+        }
+    }
+
+
+    /// Return true if the product of the values is linear, which is the case if at most one of them uses arguments.
+    pub(crate) fn is_linear_product<'a>(values: impl IntoIterator<Item = &'a Value<T>>) -> bool where T: 'a {
+        values.into_iter().filter(|v| !v.get_arguments().is_empty()).count() <= 1
+    }
+}
+
+
+
+/// Return the linear constraints on `e` (as a disjunction of conjunctions) that are verified when its value
+/// is not in the set, or when it is if `outside` is false. Return None if they overflow `T`.
+fn set_constraints<T>(e: LinearExpr<T>, set: &[T], outside: bool) -> Option<Vec<Vec<Constraint<T>>>>
+where T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked
+{
+    let mut points = set.to_vec();
+    points.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+    points.dedup();
+
+    let diff = |x: &T| e.clone().checked_sub(LinearExpr::constant(x.clone()));
+    let opposite = |x: &T| diff(x)?.checked_neg();
+
+    // each element is a case
+    if !outside {
+        return points.iter().map(|x| Some(vec![Constraint::new(diff(x)?, false), Constraint::new(opposite(x)?, false)])).collect()
+    }
+
+    // each gap between two consecutive elements is a case
+    let mut res = vec![];
+    let mut lower: Option<&T> = None;
+    for x in points.iter().map(Some).chain(std::iter::once(None)) {
+        let mut case = vec![];
+        if let Some(l) = lower {case.push(Constraint::new(opposite(l)?, true));}
+        if let Some(g) = x {case.push(Constraint::new(diff(g)?, true));}
+        res.push(case);
+        lower = x;
+    }
+    Some(res)
+}
+
+
+
+/// Return the value obtained by replacing the arguments by their value in the environment,
+/// which must be a number to be compared.
+fn evaluate_number<T: Num + PartialOrd + Clone + Display + Checked>(v: &Value<T>, env: &impl Environment<T>) -> Result<T, EvalError> {
+    let x = v.evaluate(env)?;
+    if is_nan(&x) {Err(EvalError::NotANumber(v.to_string()))}
+    else {Ok(x)}
 }
 
 
@@ -48,9 +151,24 @@ pub enum Predicate<T: Num + PartialOrd> {
     GreaterEqual(Value<T>, Value<T>),
     Equal(Value<T>, Value<T>),
 
+    /// The value is one of the elements of the set (an empty set makes the predicate false)
+    In(Value<T>, Vec<T>),
+
+    /// The value is none of the elements of the set
+    NotIn(Value<T>, Vec<T>),
+
     Not(Box<Predicate<T>>),
     And(Box<Predicate<T>>, Box<Predicate<T>>),
-    Or(Box<Predicate<T>>, Box<Predicate<T>>)
+    Or(Box<Predicate<T>>, Box<Predicate<T>>),
+
+    /// The second predicate is true whenever the first one is (`a => b`)
+    Implies(Box<Predicate<T>>, Box<Predicate<T>>),
+
+    /// The two predicates have the same truth value (`a <=> b`)
+    Iff(Box<Predicate<T>>, Box<Predicate<T>>),
+
+    /// Exactly one of the two predicates is true (`a xor b`)
+    Xor(Box<Predicate<T>>, Box<Predicate<T>>)
 }
 
 
@@ -58,6 +176,13 @@ pub enum Predicate<T: Num + PartialOrd> {
 
 impl Predicate<f64> {
     /// Return a predicate from an infix predicate string.
+    /// If the string is not valid, the returned [ParseError] describes where and why.
+    ///
+    /// Values are rounded by `f64` (`0.1 + 0.2` is not `0.3`): when boundaries must be exact,
+    /// prefer [Predicate::parse] with `num::BigRational`.
+    ///
+    /// `NaN` literals are rejected, as they can not be compared. `inf` and `-inf` can be used as bounds
+    /// that no argument reaches: arguments are considered to only have finite values (`x < inf` is always true).
     /// 
     /// # Example
     /// ```
@@ -65,125 +190,131 @@ impl Predicate<f64> {
     /// 
     /// let p = Predicate::from("(x > 5) && (x < 10)").unwrap();
     /// ```
-    pub fn from(txt: &str) -> Result<Predicate<f64>, String> {
-        parse_predicate(txt)
+    pub fn from(txt: &str) -> Result<Predicate<f64>, ParseError> {
+        parse_predicate(txt, None, Dialect::default())
     }
 }
 
 
 
-impl<T: Num + PartialOrd + Clone + ToPrimitive + Display + Debug> Predicate<T> {
-    /// Return the domain representing the values of the given argument where the Predicate is true.
-    /// Thus, the "validity domain" of a predicate is made of one domain for each of its arguments.
-    /// A Predicate with no arguments could be simplified to a single boolean value, so this function would not be useful.
-    pub fn get_domain(&self, arg_name: &str) -> Domain<T> {
+impl<T: Num + PartialOrd + Clone + FromStr> Predicate<T> {
+    /// Return a predicate from an infix predicate string, its literals being read with the [FromStr]
+    /// implementation of `T`, or as exact decimal numbers (like `0.1`) when it does not read them.
+    /// If the string is not valid, the returned [ParseError] describes where and why.
+    ///
+    /// Operators can also be written with keywords (`and`, `or`, `not`) or Unicode symbols
+    /// (`≤`, `≥`, `≠`, `∧`, `∨`, `¬`, `⇒`, `⇔`, `⊕`), and tokens do not need to be separated by spaces.
+    ///
+    /// `num::BigRational` is the recommended type when the results must be exact:
+    /// its literals can be written as decimals or fractions (like `1/3`), and nothing is ever rounded.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::{Predicate, Implication};
+    ///
+    /// // too large to be exactly represented by a f64
+    /// let a = Predicate::<i64>::parse("id == 9007199254740993").unwrap();
+    /// let b = Predicate::<i64>::parse("id > 9007199254740992").unwrap();
+    /// assert_eq!(a.implies(&b), Implication::Total);
+    ///
+    /// // exact rational numbers
+    /// let a = Predicate::<num::BigRational>::parse("x <= 0.1 + 0.2").unwrap();
+    /// let b = Predicate::<num::BigRational>::parse("x <= 0.3").unwrap();
+    /// assert_eq!(a.implies(&b), Implication::Total);
+    ///
+    /// let a = Predicate::<num::BigRational>::parse("3 * x <= 1/3").unwrap();
+    /// let b: Predicate<num::BigRational> = "x <= 1/9".parse().unwrap();
+    /// assert_eq!(a.implies(&b), Implication::Total);
+    /// ```
+    pub fn parse(txt: &str) -> Result<Predicate<T>, ParseError> {
+        parse_predicate(txt, None, Dialect::default())
+    }
 
-        match self {
-            Predicate::True => Domain::_true(),
-            Predicate::False => Domain::_false(),
-
-            // if _ is arg_name: unknown value so the validity domain could be anything
-            // if _ is not arg_name: the value of arg_name is irrelevant so the domain of validity is any values
-            Predicate::BoolArg(_) => Domain::_true(),
-
-            Predicate::LowerThan(v1, v2) => {
-                match (v1, v2) {
-                    (Value::Arg(a1), Value::Arg(a2)) => {
-                        if a1 == a2 {Domain::_false()} else {Domain::_true()} // the same argument cannot be lower than itself, so the Predicate is always false
-                    },
-
-                    (Value::Arg(a), Value::Literal(x)) => {
-                        if a == arg_name {Domain::new(None, false, Some(x.clone()), false)}
-                        else {Domain::_true()}
-                    },
-
-                    (Value::Literal(x), Value::Arg(a)) => {
-                        if a == arg_name {Domain::new(Some(x.clone()), false, None, false)}
-                        else {Domain::_true()}
-                    },
-
-                    (Value::Literal(x1), Value::Literal(x2)) => if x1 < x2 {Domain::_true()} else {Domain::_false()},
-                }
-            },
 
+    /// Same as [Predicate::parse], the equality being written as defined by the [Dialect].
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::{Predicate, Dialect};
+    ///
+    /// let p = Predicate::<f64>::parse_with("x = 5 ∧ y ≤ 2", Dialect::SingleEquals).unwrap();
+    /// assert_eq!(p, Predicate::from("(x == 5) && (y <= 2)").unwrap());
+    /// assert!(Predicate::<f64>::parse("x = 5").is_err());
+    /// ```
+    pub fn parse_with(txt: &str, dialect: Dialect) -> Result<Predicate<T>, ParseError> {
+        parse_predicate(txt, None, dialect)
+    }
+}
 
-            Predicate::LowerEqual(v1, v2) => {
-                match (v1, v2) {
-                    (Value::Arg(_), Value::Arg(_)) => Domain::_true(),
-
-                    (Value::Arg(a), Value::Literal(x)) => {
-                        if a == arg_name {Domain::new(None, false, Some(x.clone()), true)}
-                        else {Domain::_true()}
-                    },
-                    (Value::Literal(x), Value::Arg(a)) => {
-                        if a == arg_name {Domain::new(Some(x.clone()), true, Some(x.clone()), false)}
-                        else {Domain::_true()}
-                    },
-                    (Value::Literal(x1), Value::Literal(x2)) => if x1 <= x2 {Domain::_true()} else {Domain::_false()},
-                }
-            },
 
 
-            Predicate::GreaterThan(v1, v2) => {
-                match (v1, v2) {
-                    (Value::Arg(a1), Value::Arg(a2)) => {
-                        if a1 == a2 {Domain::_false()} else {Domain::_true()} // the same argument cannot be greater than itself, so the Predicate is always false
-                    },
-
-                    (Value::Arg(a), Value::Literal(x)) => {
-                        if a == arg_name {Domain::new(Some(x.clone()), false, None, false)}
-                        else {Domain::_true()}
-                    },
-                    (Value::Literal(x), Value::Arg(a)) => {
-                        if a == arg_name {Domain::new(None, false, Some(x.clone()), false)}
-                        else {Domain::_true()}
-                    },
-                    (Value::Literal(x1), Value::Literal(x2)) => if x1 > x2 {Domain::_true()} else {Domain::_false()},
-                }
-            },
+/// Same as [Predicate::parse].
+impl<T: Num + PartialOrd + Clone + FromStr> FromStr for Predicate<T> {
+    type Err = ParseError;
+
+    fn from_str(txt: &str) -> Result<Predicate<T>, ParseError> {
+        parse_predicate(txt, None, Dialect::default())
+    }
+}
 
 
-            Predicate::GreaterEqual(v1, v2) => {
-                match (v1, v2) {
-                    (Value::Arg(_), Value::Arg(_)) => Domain::_true(),
-                    (Value::Arg(a), Value::Literal(x)) => {
-                        if a == arg_name {Domain::new(Some(x.clone()), true, None, false)}
-                        else {Domain::_true()}
-                    },
-                    (Value::Literal(x), Value::Arg(a)) => {
-                        if a == arg_name {Domain::new(None, false, Some(x.clone()), true)}
-                        else {Domain::_true()}
-                    },
-                    (Value::Literal(x1), Value::Literal(x2)) => if x1 >= x2 {Domain::_true()} else {Domain::_false()},
-                }
-            },
 
+impl<T: Num + PartialOrd + Clone + Display + Checked> Predicate<T> {
+    /// Return the truth value of the predicate when its arguments have the values given by the environment.
+    ///
+    /// As with the `&&` and `||` operators of Rust, the second operand of `And`, `Or` and `Implies` is only evaluated
+    /// when the first one is not enough to know the result, so its arguments might not need a value.
+    ///
+    /// A comparison of a value that is not a number (like `f64::NAN`) is neither true nor false: it is an error.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    /// use predicatechecker::{Predicate, EvalError};
+    ///
+    /// let p = Predicate::from("(x > 5) && (x + y < 10)").unwrap();
+    ///
+    /// let env = HashMap::from([("x".to_string(), 6.0), ("y".to_string(), 1.0)]);
+    /// assert_eq!(p.evaluate(&env), Ok(true));
+    ///
+    /// let env = HashMap::from([("x".to_string(), 6.0)]);
+    /// assert_eq!(p.evaluate(&env), Err(EvalError::Unbound("y".to_string())));
+    /// ```
+    pub fn evaluate(&self, env: &impl Environment<T>) -> Result<bool, EvalError> {
+        match self {
+            Predicate::True => Ok(true),
+            Predicate::False => Ok(false),
+
+            Predicate::BoolArg(a) => match env.get_bool(a) {
+                Some(b) => Ok(b),
+                None if env.get_value(a).is_some() => Err(EvalError::WrongType(a.clone())),
+                None => Err(EvalError::Unbound(a.clone()))
+            },
 
-            Predicate::Equal(v1, v2) => {
-                match (v1, v2) {
-                    (Value::Arg(_), Value::Arg(_)) => Domain::_true(),
-                    (Value::Arg(a), Value::Literal(x)) => {
-                        if a == arg_name {Domain::point(x.clone())}
-                        else {Domain::_true()}
-                    },
-                    (Value::Literal(x), Value::Arg(a)) => {
-                        if a == arg_name {Domain::point(x.clone())}
-                        else {Domain::_true()}
-                    },
-                    (Value::Literal(x1), Value::Literal(x2)) => if x1 == x2 {Domain::_true()} else {Domain::_false()},
-                }
+            Predicate::LowerThan(v1, v2) => Ok(evaluate_number(v1, env)? < evaluate_number(v2, env)?),
+            Predicate::LowerEqual(v1, v2) => Ok(evaluate_number(v1, env)? <= evaluate_number(v2, env)?),
+            Predicate::GreaterThan(v1, v2) => Ok(evaluate_number(v1, env)? > evaluate_number(v2, env)?),
+            Predicate::GreaterEqual(v1, v2) => Ok(evaluate_number(v1, env)? >= evaluate_number(v2, env)?),
+            Predicate::Equal(v1, v2) => Ok(evaluate_number(v1, env)? == evaluate_number(v2, env)?),
+            Predicate::In(v, set) => {
+                let x = evaluate_number(v, env)?;
+                Ok(set.contains(&x))
+            },
+            Predicate::NotIn(v, set) => {
+                let x = evaluate_number(v, env)?;
+                Ok(!set.contains(&x))
             },
 
-            Predicate::Not(p) => Domain::complement(p.get_domain(arg_name)),
-            Predicate::And(p1, p2) => Domain::intersection(p1.get_domain(arg_name), p2.get_domain(arg_name)),
-            Predicate::Or(p1, p2) => Domain::union(p1.get_domain(arg_name), p2.get_domain(arg_name)),
+            Predicate::Not(p) => Ok(!p.evaluate(env)?),
+            Predicate::And(p1, p2) => Ok(p1.evaluate(env)? && p2.evaluate(env)?),
+            Predicate::Or(p1, p2) => Ok(p1.evaluate(env)? || p2.evaluate(env)?),
+            Predicate::Implies(p1, p2) => Ok(!p1.evaluate(env)? || p2.evaluate(env)?),
+            Predicate::Iff(p1, p2) => Ok(p1.evaluate(env)? == p2.evaluate(env)?),
+            Predicate::Xor(p1, p2) => Ok(p1.evaluate(env)? != p2.evaluate(env)?),
         }
     }
 
 
-
-
-
     /// Return the set of arguments used by the predicate
     pub fn get_arguments(&self) -> HashSet<String> {
         match self {
@@ -194,119 +325,419 @@ impl<T: Num + PartialOrd + Clone + ToPrimitive + Display + Debug> Predicate<T> {
                 set.insert(a.clone());
                 set
             },
-            Predicate::LowerThan(v1, v2) => {
-                let mut set = HashSet::new();
-                match v1 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                match v2 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                set
-            },
-            Predicate::LowerEqual(v1, v2) => {
-                let mut set = HashSet::new();
-                match v1 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                match v2 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                set
-            },
-            Predicate::GreaterThan(v1, v2) => {
-                let mut set = HashSet::new();
-                match v1 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                match v2 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                set
-            },
-            Predicate::GreaterEqual(v1, v2) => {
-                let mut set = HashSet::new();
-                match v1 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                match v2 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                set
-            },
-            Predicate::Equal(v1, v2) => {
-                let mut set = HashSet::new();
-                match v1 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
-                match v2 {
-                    Value::Arg(a) => set.insert(a.clone()),
-                    Value::Literal(_) => false,
-                };
+            Predicate::LowerThan(v1, v2)
+            | Predicate::LowerEqual(v1, v2)
+            | Predicate::GreaterThan(v1, v2)
+            | Predicate::GreaterEqual(v1, v2)
+            | Predicate::Equal(v1, v2) => {
+                let mut set = v1.get_arguments();
+                set.extend(v2.get_arguments());
                 set
             },
+            Predicate::In(v, _) | Predicate::NotIn(v, _) => v.get_arguments(),
             Predicate::Not(p) => p.get_arguments(),
             Predicate::And(p1, p2) => {
                 let mut set = p1.get_arguments();
                 for a in p2.get_arguments() {set.insert(a);}
                 set
             },
-            Predicate::Or(p1, p2) => {
+            Predicate::Or(p1, p2)
+            | Predicate::Implies(p1, p2)
+            | Predicate::Iff(p1, p2)
+            | Predicate::Xor(p1, p2) => {
                 let mut set = p1.get_arguments();
                 for a in p2.get_arguments() {set.insert(a);}
                 set
             },
         }
     }
+}
 
 
 
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> Predicate<T> {
+    /// Return the domain representing the values of the given argument where the Predicate can be true,
+    /// i.e. the values for which there exist values of the other arguments making the Predicate true.
+    /// Thus, the "validity domain" of a predicate is made of one domain for each of its arguments.
+    /// A Predicate with no arguments could be simplified to a single boolean value, so this function would not be useful.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// // x can have any value, as long as y is positive when x is not
+    /// let p = Predicate::from("(x > 0) || (y > 0)").unwrap();
+    /// assert_eq!(p.get_domain("x"), Predicate::True.get_domain("x"));
+    ///
+    /// // x and y must be greater than 0 at the same time, so x can not be greater than 1
+    /// let p = Predicate::from("(x > 0) && (y > 0) && (x + y < 1)").unwrap();
+    /// assert_eq!(p.get_domain("x"), Predicate::from("(x > 0) && (x < 1)").unwrap().get_domain("x"));
+    /// ```
+    pub fn get_domain(&self, arg_name: &str) -> Domain<T> {
+        self.get_domain_in(arg_name, &Schema::new())
+    }
+
+
+    /// Same as [Predicate::get_domain], the arguments only having the values allowed by the schema.
+    pub(crate) fn get_domain_in(&self, arg_name: &str, schema: &Schema<T>) -> Domain<T> {
+        solver::get_domain(&[(self, true)], arg_name, schema)
+    }
+
+
+
+
+
+    /// Return the linear constraints equivalent to a comparison (or to its negation),
+    /// as a disjunction of conjunctions of constraints.
+    /// Return None if the predicate is not a comparison, or if the comparison is not linear or overflows `T`.
+    pub(crate) fn get_constraints(&self, negated: bool) -> Option<Vec<Vec<Constraint<T>>>> {
+        if let Predicate::In(v, set) | Predicate::NotIn(v, set) = self {
+            let e = LinearExpr::from_value(v)?;
+            return set_constraints(e, set, negated == matches!(self, Predicate::In(..)))
+        }
+
+        let (v1, v2) = match self {
+            Predicate::LowerThan(v1, v2)
+            | Predicate::LowerEqual(v1, v2)
+            | Predicate::GreaterThan(v1, v2)
+            | Predicate::GreaterEqual(v1, v2)
+            | Predicate::Equal(v1, v2) => (v1, v2),
+            _ => return None
+        };
+
+        // every comparison is made between v1 - v2 and 0
+        let e = LinearExpr::from_value(v1)?.checked_sub(LinearExpr::from_value(v2)?)?;
+        let opposite = e.clone().checked_neg()?;
+
+        let res = match (self, negated) {
+            (Predicate::LowerThan(..), false) | (Predicate::GreaterEqual(..), true) => vec![vec![Constraint::new(e, true)]],
+            (Predicate::LowerEqual(..), false) | (Predicate::GreaterThan(..), true) => vec![vec![Constraint::new(e, false)]],
+            (Predicate::GreaterThan(..), false) | (Predicate::LowerEqual(..), true) => vec![vec![Constraint::new(opposite, true)]],
+            (Predicate::GreaterEqual(..), false) | (Predicate::LowerThan(..), true) => vec![vec![Constraint::new(opposite, false)]],
+
+            (_, false) => vec![vec![Constraint::new(e, false), Constraint::new(opposite, false)]],
+            (_, true) => vec![vec![Constraint::new(e, true)], vec![Constraint::new(opposite, true)]],
+        };
+
+        Some(res)
+    }
+
+
 
 
 
     /// Return the level of [Implication] between two predicates A (self) and B (other).
+    ///
+    /// Arguments can be compared with each other, and compared values can be linear expressions:
+    ///
+    /// ```
+    /// use predicatechecker::{Predicate, Implication};
+    ///
+    /// let a = Predicate::from("(x < y) && (y < 5)").unwrap();
+    /// let b = Predicate::from("x < 5").unwrap();
+    /// assert_eq!(a.implies(&b), Implication::Total);
+    ///
+    /// let a = Predicate::from("(2 * x + y <= 10) && (y >= 4)").unwrap();
+    /// let b = Predicate::from("x <= 3").unwrap();
+    /// assert_eq!(a.implies(&b), Implication::Total);
+    /// assert_eq!(b.implies(&a), Implication::Partial { coverage: None });
+    /// assert_eq!(b.implies(&Predicate::from("x > 3").unwrap()), Implication::Inexistant);
+    /// ```
+    ///
+    /// The arguments are not considered independently from each other, so disjunctions are handled soundly:
+    ///
+    /// ```
+    /// use predicatechecker::{Predicate, Implication};
+    ///
+    /// let a = Predicate::from("(x > 0) && (y > 0)").unwrap();
+    /// let b = Predicate::from("(x > 5) || (y > 5)").unwrap();
+    /// assert_eq!(a.implies(&b), Implication::Partial { coverage: None });
+    /// ```
+    ///
+    /// Arguments used as predicates are boolean arguments:
+    ///
+    /// ```
+    /// use predicatechecker::{Predicate, Implication};
+    ///
+    /// let a = Predicate::from("enabled && (x > 1)").unwrap();
+    /// assert_eq!(a.implies(&Predicate::BoolArg("enabled".to_string())), Implication::Total);
+    ///
+    /// let b = Predicate::from("(enabled || (x > 5)) && (x < 0)").unwrap();
+    /// assert_eq!(b.implies(&a), Implication::Inexistant);
+    /// assert_eq!(b.implies(&Predicate::from("enabled").unwrap()), Implication::Total);
+    /// ```
+    ///
+    /// If `T` is an integer type, the arguments can only have integer values:
+    ///
+    /// ```
+    /// use predicatechecker::{Predicate, Implication};
+    ///
+    /// let a = Predicate::<i64>::parse("x > 5").unwrap();
+    /// assert_eq!(a.implies(&Predicate::parse("x >= 6").unwrap()), Implication::Total);
+    ///
+    /// let a = Predicate::<i64>::parse("(x > 5) && (x < 6)").unwrap();
+    /// assert!(!a.is_satisfiable());
+    /// ```
+    ///
+    /// With bounded integer types, a comparison that can not be reasoned about without overflowing `T`
+    /// (like `x < -9223372036854775808` with `i64`) can have any truth value, like non-linear ones.
+    ///
+    /// The reasoning negates values, so `T` must be a signed type: unsigned integers are not supported
+    /// (predicates over them can still be parsed, displayed, evaluated and put in normal form).
+    ///
+    /// ```compile_fail
+    /// use predicatechecker::Predicate;
+    ///
+    /// let a = Predicate::<u64>::parse("x > 5").unwrap();
+    /// a.implies(&Predicate::parse("x >= 6").unwrap());
+    /// ```
+    ///
+    /// When only some of the values verifying A verify B, the implication is [Implication::Partial],
+    /// with the proportion of the values verifying A that verify B (see [Predicate::coverage]):
+    ///
+    /// ```
+    /// use predicatechecker::{Predicate, Implication};
+    ///
+    /// let a = Predicate::from("((x >= 0) && (x <= 1)) || ((x >= 2) && (x <= 5))").unwrap();
+    /// assert_eq!(a.implies(&Predicate::from("x < 1.5").unwrap()), Implication::Partial { coverage: Some(0.25) });
+    /// assert_eq!(a.implies(&Predicate::from("x > 6").unwrap()), Implication::Inexistant);
+    ///
+    /// let a = Predicate::from("(x < 0) || (x > 10)").unwrap();
+    /// assert_eq!(a.implies(&Predicate::from("x > 10").unwrap()), Implication::Partial { coverage: None });
+    /// ```
     pub fn implies(&self, other: &Predicate<T>) -> Implication {
+        self.implies_in(other, &Schema::new())
+    }
+
+
+    /// Same as [Predicate::implies], the arguments only having the values allowed by the schema.
+    pub(crate) fn implies_in(&self, other: &Predicate<T>, schema: &Schema<T>) -> Implication {
         // A is self, B is other
 
-        let self_args = self.get_arguments();
-        let other_args = other.get_arguments();
+        // A implies B if there are no values for which A is true and B is false,
+        // and does not at all if there are no values for which both are true
+        if !solver::is_satisfiable(&[(self, true), (other, false)], schema) {Implication::Total}
+        else if !solver::is_satisfiable(&[(self, true), (other, true)], schema) {Implication::Inexistant}
+        else {Implication::Partial { coverage: self.coverage_in(other, schema).filter(|c| *c > 0.0 && *c < 1.0) }}
+    }
+
+
+    /// Return true if there are values of the arguments for which the predicate is true.
+    ///
+    /// Comparisons that are not linear cannot be reasoned about, so in their presence
+    /// the predicate might be considered satisfiable when it is not.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let p = Predicate::from("((x > 0) || (y > 0)) && (x + y < 0)").unwrap();
+    /// assert!(p.is_satisfiable());
+    ///
+    /// let p = Predicate::from("(x < y) && (y < z) && (z < x)").unwrap();
+    /// assert!(p.is_contradiction());
+    ///
+    /// let p = Predicate::from("(x <= y) || (y <= x)").unwrap();
+    /// assert!(p.is_valid());
+    ///
+    /// let a = Predicate::from("(x > 5) || (x > 10)").unwrap();
+    /// let b = Predicate::from("x - 5 > 0").unwrap();
+    /// assert!(a.equivalent(&b));
+    /// ```
+    pub fn is_satisfiable(&self) -> bool {
+        solver::is_satisfiable(&[(self, true)], &Schema::new())
+    }
+
+
+    /// Return true if the predicate is true whatever the values of its arguments (i.e. it is a tautology).
+    pub fn is_valid(&self) -> bool {
+        !solver::is_satisfiable(&[(self, false)], &Schema::new())
+    }
+
+
+    /// Return true if the predicate is false whatever the values of its arguments.
+    pub fn is_contradiction(&self) -> bool {
+        !self.is_satisfiable()
+    }
+
+
+    /// Return true if the two predicates have the same truth value whatever the values of their arguments.
+    pub fn equivalent(&self, other: &Predicate<T>) -> bool {
+        let schema = Schema::new();
+        !solver::is_satisfiable(&[(self, true), (other, false)], &schema) && !solver::is_satisfiable(&[(self, false), (other, true)], &schema)
+    }
+
+
+
 
 
-        // special case for Or
+
+    /// Return the level of [Implication] between two predicates A (self) and B (other), and if A does not
+    /// imply B totally, a counterexample: values of the arguments of A and B for which A is true but B is not.
+    ///
+    /// The counterexample can be missing even if the implication is not total: the values are searched among
+    /// the real numbers then rounded, which can fail with floats and integers (the elimination of arguments is
+    /// not exact over integers), and a counterexample that does not verify the comparisons that are not linear
+    /// is dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::{Predicate, Implication};
+    ///
+    /// let a = Predicate::from("(x > 5) && (x < 10)").unwrap();
+    /// let b = Predicate::from("x > 6").unwrap();
+    ///
+    /// let (implication, witness) = a.implies_with_witness(&b);
+    /// assert_eq!(implication, Implication::Partial { coverage: Some(0.8) });
+    ///
+    /// let x = *witness.unwrap().get_value("x").unwrap();
+    /// assert!(x > 5.0 && x <= 6.0);
+    /// ```
+    pub fn implies_with_witness(&self, other: &Predicate<T>) -> (Implication, Option<Assignment<T>>) {
+        self.implies_with_witness_in(other, &Schema::new())
+    }
+
+
+    /// Same as [Predicate::implies_with_witness], the arguments only having the values allowed by the schema.
+    pub(crate) fn implies_with_witness_in(&self, other: &Predicate<T>, schema: &Schema<T>) -> (Implication, Option<Assignment<T>>) {
+        match self.implies_in(other, schema) {
+            Implication::Total => (Implication::Total, None),
+            i => {
+                // the search does not reason about non-linear comparisons, so the values are checked
+                let witness = solver::find_model(&[(self, true), (other, false)], schema)
+                    .filter(|w| self.evaluate(w) == Ok(true) && other.evaluate(w) == Ok(false));
+
+                (i, witness)
+            }
+        }
+    }
+}
+
+
+
+
+impl<T: Num + PartialOrd + Display> Value<T> {
+    /// Return the precedence of the operator of the value in the parser: operators with a higher precedence are applied first.
+    fn precedence(&self) -> u8 {
+        match self {
+            Value::Add(..) | Value::Sub(..) => 3,
+            Value::Mul(..) => 4,
+            Value::Neg(_) => 5,
+            Value::Arg(_) | Value::Literal(_) => 6
+        }
+    }
+}
+
+
+
+/// Write a value or a predicate, between parentheses if needed.
+fn write_operand(f: &mut std::fmt::Formatter<'_>, operand: &impl Display, parens: bool) -> std::fmt::Result {
+    if parens {write!(f, "({operand})")}
+    else {write!(f, "{operand}")}
+}
+
+
+
+/// Infix notation, with only the parentheses needed for [Predicate::from] to read it back.
+impl<T: Num + PartialOrd + Display> Display for Value<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (v1, op, v2) = match self {
+            Value::Arg(a) => return write!(f, "{a}"),
+            Value::Literal(x) => return write!(f, "{x}"),
+            Value::Neg(v) => {
+                write!(f, "-")?;
+                return if v.precedence() < 5 {write_operand(f, v, true)} else {write!(f, " {v}")}
+            },
+
+            Value::Add(v1, v2) => (v1, "+", v2),
+            Value::Sub(v1, v2) => (v1, "-", v2),
+            Value::Mul(v1, v2) => (v1, "*", v2),
+        };
+
+        // the operators are left-associative: the right operand needs parentheses if its operator has the same precedence
+        write_operand(f, v1, v1.precedence() < self.precedence())?;
+        write!(f, " {op} ")?;
+        write_operand(f, v2, v2.precedence() <= self.precedence())
+    }
+}
+
+
+
+
+impl<T: Num + PartialOrd + Display> Predicate<T> {
+    /// Return the precedence of the operator of the predicate in the parser: operators with a higher precedence are applied first.
+    fn precedence(&self) -> u8 {
         match self {
-            Predicate::Or(lp, rp) => {
-                match (lp.implies(other), rp.implies(other)) {
-                    (Implication::Total, Implication::Total) => Implication::Total,
-                    (Implication::Inexistant, Implication::Inexistant) => Implication::Inexistant,
-                    _ => Implication::Partial
-                }
+            Predicate::Implies(..) | Predicate::Iff(..) => 0,
+            Predicate::And(..) | Predicate::Or(..) | Predicate::Xor(..) => 1,
+            Predicate::Not(_) => 5,
+            Predicate::True | Predicate::False | Predicate::BoolArg(_) => 6,
+            _ => 2
+        }
+    }
+}
+
+
+
+/// Infix notation, with only the parentheses needed for [Predicate::from] to read it back,
+/// except between `&&`, `||` and `xor` (which have the same precedence) and around the operands of `=>`
+/// and `<=>` using them (`(a => b) => c`) to keep it readable.
+///
+/// # Example
+/// ```
+/// use predicatechecker::Predicate;
+///
+/// let p = Predicate::from("((x > 5) && (2 * (y - 1) <= x)) || enabled").unwrap();
+/// assert_eq!(p.to_string(), "(x > 5 && 2 * (y - 1) <= x) || enabled");
+/// assert_eq!(Predicate::from(&p.to_string()).unwrap(), p);
+/// ```
+impl<T: Num + PartialOrd + Display> Display for Predicate<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (v1, op, v2) = match self {
+            Predicate::True => return write!(f, "true"),
+            Predicate::False => return write!(f, "false"),
+            Predicate::BoolArg(a) => return write!(f, "{a}"),
+
+            Predicate::Not(p) => {
+                write!(f, "!")?;
+                return if p.precedence() < 5 {write_operand(f, p, true)} else {write!(f, " {p}")}
+            },
+
+            Predicate::And(p1, p2) | Predicate::Or(p1, p2) | Predicate::Xor(p1, p2) => {
+                let op = match self {
+                    Predicate::And(..) => "&&",
+                    Predicate::Or(..) => "||",
+                    _ => "xor"
+                };
+                let mixed = |p: &Predicate<T>| p.precedence() < 1 || (p.precedence() == 1 && std::mem::discriminant(p) != std::mem::discriminant(self));
+
+                write_operand(f, p1, mixed(p1))?;
+                write!(f, " {op} ")?;
+                return write_operand(f, p2, p2.precedence() <= 1)
             },
 
-            _ => {
-                // Check that B uses only arguments used by A
-                for a in other_args.iter() {
-                    if !self_args.contains(a) {return Implication::Inexistant;}
-                }
+            Predicate::Implies(p1, p2) | Predicate::Iff(p1, p2) => {
+                let op = if let Predicate::Implies(..) = self {"=>"} else {"<=>"};
 
-                // Check validity domains for arguments used both by A and B
-                for a in other_args.iter() {
-                    let d1 = self.get_domain(a);
-                    let d2 = other.get_domain(a);
+                write_operand(f, p1, p1.precedence() == 0)?;
+                write!(f, " {op} ")?;
+                return write_operand(f, p2, p2.precedence() == 0)
+            },
 
-                    if Domain::union(d1, d2.clone()) != d2 {return Implication::Inexistant;}
-                }
+            Predicate::LowerThan(v1, v2) => (v1, "<", v2),
+            Predicate::LowerEqual(v1, v2) => (v1, "<=", v2),
+            Predicate::GreaterThan(v1, v2) => (v1, ">", v2),
+            Predicate::GreaterEqual(v1, v2) => (v1, ">=", v2),
+            Predicate::Equal(v1, v2) => (v1, "==", v2),
 
-                Implication::Total
+            Predicate::In(v, set) | Predicate::NotIn(v, set) => {
+                let op = if let Predicate::In(..) = self {"in"} else {"not in"};
+                let set: Vec<String> = set.iter().map(|x| x.to_string()).collect();
+                return write!(f, "{v} {op} {{{}}}", set.join(", "))
             }
-        }
+        };
+
+        // values always have a higher precedence than comparisons
+        write!(f, "{v1} {op} {v2}")
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,157 @@
+//! Types of the arguments of predicates.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Debug};
+use std::str::FromStr;
+
+use num::{Num, Signed, ToPrimitive};
+
+use crate::arithmetic::Checked;
+use crate::assignment::Assignment;
+use crate::domain::Domain;
+use crate::error::ParseError;
+use crate::parser::{parse_predicate, Dialect};
+use crate::predicate::{Predicate, Implication};
+
+
+
+/// Type of an argument of a [Predicate].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type<T> {
+    Bool,
+    Int,
+    Real,
+
+    /// Integers between two included bounds (None meaning that there is no bound)
+    IntRange(Option<T>, Option<T>),
+
+    /// Numbers between two included bounds (None meaning that there is no bound)
+    RealRange(Option<T>, Option<T>)
+}
+
+
+
+impl<T> Type<T> {
+    /// Return true if the arguments of this type are boolean arguments.
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Type::Bool)
+    }
+
+    /// Return true if the arguments of this type can only have integer values.
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Type::Int | Type::IntRange(..))
+    }
+}
+
+
+
+
+/// Types of the arguments of predicates.
+///
+/// A schema rejects the predicates using arguments that are not declared, or in a way that does not match their type,
+/// and restricts the values considered when reasoning about predicates to the ones allowed by the types of their arguments.
+///
+/// # Example
+/// ```
+/// use predicatechecker::{Schema, Type, Implication};
+///
+/// let mut schema = Schema::new();
+/// schema.declare("age", Type::IntRange(Some(0.0), Some(150.0)));
+/// schema.declare("premium", Type::Bool);
+///
+/// let a = schema.parse("premium && (age < 1)").unwrap();
+/// let b = schema.parse("age == 0").unwrap();
+/// assert_eq!(schema.implies(&a, &b), Implication::Total);
+/// assert_eq!(a.implies(&b), Implication::Partial { coverage: None });
+///
+/// assert!(schema.parse("age && premium").is_err());
+/// assert!(schema.parse("height > 150").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema<T> {
+    types: HashMap<String, Type<T>>
+}
+
+
+
+impl<T> Default for Schema<T> {
+    fn default() -> Self {
+        Schema::new()
+    }
+}
+
+
+
+impl<T> Schema<T> {
+    /// Create a schema with no arguments.
+    pub fn new() -> Schema<T> {
+        Schema { types: HashMap::new() }
+    }
+
+
+    /// Declare an argument with its type.
+    pub fn declare(&mut self, arg: &str, t: Type<T>) {
+        self.types.insert(arg.to_string(), t);
+    }
+
+
+    /// Return the type of an argument, if it is declared.
+    pub fn get_type(&self, arg: &str) -> Option<&Type<T>> {
+        self.types.get(arg)
+    }
+}
+
+
+
+impl<T: Num + PartialOrd + Clone + FromStr> Schema<T> {
+    /// Return a predicate from an infix predicate string (see [Predicate::parse]),
+    /// checking that every argument is declared and used according to its type.
+    pub fn parse(&self, txt: &str) -> Result<Predicate<T>, ParseError> {
+        parse_predicate(txt, Some(self), Dialect::default())
+    }
+
+
+    /// Same as [Schema::parse], the equality being written as defined by the [Dialect].
+    pub fn parse_with(&self, txt: &str, dialect: Dialect) -> Result<Predicate<T>, ParseError> {
+        parse_predicate(txt, Some(self), dialect)
+    }
+}
+
+
+
+impl<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked> Schema<T> {
+    /// Return a predicate on the argument which is true for the values allowed by its range,
+    /// or None if its type is not a range.
+    pub fn get_range(&self, arg: &str) -> Option<Predicate<T>> {
+        match self.get_type(arg)? {
+            Type::IntRange(l, g) | Type::RealRange(l, g) => {
+                Some(Domain::new(l.clone(), true, g.clone(), true).to_predicate(arg))
+            },
+            _ => None
+        }
+    }
+
+
+    /// Same as [Predicate::implies], the arguments only having the values allowed by their type.
+    pub fn implies(&self, a: &Predicate<T>, b: &Predicate<T>) -> Implication {
+        a.implies_in(b, self)
+    }
+
+
+    /// Same as [Predicate::implies_with_witness], the arguments only having the values allowed by their type.
+    pub fn implies_with_witness(&self, a: &Predicate<T>, b: &Predicate<T>) -> (Implication, Option<Assignment<T>>) {
+        a.implies_with_witness_in(b, self)
+    }
+
+
+    /// Same as [Predicate::coverage], the arguments only having the values allowed by their type.
+    pub fn coverage(&self, a: &Predicate<T>, b: &Predicate<T>) -> Option<f64> {
+        a.coverage_in(b, self)
+    }
+
+
+    /// Same as [Predicate::get_domain], the arguments only having the values allowed by their type.
+    pub fn get_domain(&self, p: &Predicate<T>, arg_name: &str) -> Domain<T> {
+        p.get_domain_in(arg_name, self)
+    }
+}
@@ -0,0 +1,355 @@
+//! Serialization of predicates and domains, with the `serde` feature.
+//!
+//! The format is versioned: a [Predicate], a [Value] and a [Domain] are written in an object giving the version
+//! of the format ([FORMAT_VERSION]), and objects of a later version are rejected. In JSON:
+//!
+//! ```json
+//! {"version": 2, "predicate": {"op": "gt", "left": {"op": "arg", "name": "x"}, "right": {"op": "literal", "value": 5.0}}}
+//! {"version": 2, "value": {"op": "neg", "operand": {"op": "arg", "name": "x"}}}
+//! {"version": 2, "integer": false, "intervals": [{"lower": {"value": 5.0, "inclusive": false}, "upper": null}]}
+//! ```
+//!
+//! Each node of a predicate or of a value is an object whose `op` field gives its kind:
+//! - predicates: `true`, `false`, `bool_arg` (with a `name`), the comparisons `lt`, `le`, `gt`, `ge` and `eq`
+//!   (with a `left` and a `right` value), `in` and `not_in` (with a `value` and a `set` of literals), `not` (with an `operand`),
+//!   `and`, `or`, `implies`, `iff` and `xor` (with a `left` and a `right` predicate);
+//! - values: `arg` (with a `name`), `literal` (with a `value`), `add`, `sub` and `mul` (with a `left` and a `right` value)
+//!   and `neg` (with an `operand`).
+//!
+//! Literals are written with the serde implementation of their type. An [Interval] is an object with a `lower` and an
+//! `upper` bound, each being `null` (no bound) or an object with the `value` of the bound and whether it is `inclusive`.
+//! An [Implication] is written as `"total"`, `{"partial": {"coverage": 0.5}}` (`null` if the coverage can not be measured)
+//! or `"inexistant"`.
+//!
+//! The version 2 only changed the format of a partial [Implication], written as `"partial"` by the version 1:
+//! objects of the version 1 are still read, and so is this partial implication, which coverage is unknown.
+
+use std::fmt::{Display, Debug};
+
+use num::{Num, Signed, ToPrimitive};
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::de::Error;
+
+use crate::arithmetic::Checked;
+use crate::domain::{Domain, Interval};
+use crate::predicate::{Predicate, Value, Implication};
+
+
+
+/// Version of the serialization format of predicates, values and domains.
+pub const FORMAT_VERSION: u32 = 2;
+
+
+
+/// Return an error if the version of a deserialized object is not one of this format or of a previous one.
+fn check_version<E: Error>(version: u32) -> Result<(), E> {
+    if (1..=FORMAT_VERSION).contains(&version) {Ok(())}
+    else {Err(E::custom(format!("unsupported format version {version} (expected at most {FORMAT_VERSION})")))}
+}
+
+
+
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum PredicateNode<T> {
+    True,
+    False,
+    BoolArg { name: String },
+
+    Lt { left: ValueNode<T>, right: ValueNode<T> },
+    Le { left: ValueNode<T>, right: ValueNode<T> },
+    Gt { left: ValueNode<T>, right: ValueNode<T> },
+    Ge { left: ValueNode<T>, right: ValueNode<T> },
+    Eq { left: ValueNode<T>, right: ValueNode<T> },
+    In { value: ValueNode<T>, set: Vec<T> },
+    NotIn { value: ValueNode<T>, set: Vec<T> },
+
+    Not { operand: Box<PredicateNode<T>> },
+    And { left: Box<PredicateNode<T>>, right: Box<PredicateNode<T>> },
+    Or { left: Box<PredicateNode<T>>, right: Box<PredicateNode<T>> },
+    Implies { left: Box<PredicateNode<T>>, right: Box<PredicateNode<T>> },
+    Iff { left: Box<PredicateNode<T>>, right: Box<PredicateNode<T>> },
+    Xor { left: Box<PredicateNode<T>>, right: Box<PredicateNode<T>> }
+}
+
+
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum ValueNode<T> {
+    Arg { name: String },
+    Literal { value: T },
+
+    Add { left: Box<ValueNode<T>>, right: Box<ValueNode<T>> },
+    Sub { left: Box<ValueNode<T>>, right: Box<ValueNode<T>> },
+    Mul { left: Box<ValueNode<T>>, right: Box<ValueNode<T>> },
+    Neg { operand: Box<ValueNode<T>> }
+}
+
+
+
+impl<T: Num + PartialOrd + Clone> From<&Predicate<T>> for PredicateNode<T> {
+    fn from(p: &Predicate<T>) -> Self {
+        let boxed = |p: &Predicate<T>| Box::new(PredicateNode::from(p));
+
+        match p {
+            Predicate::True => PredicateNode::True,
+            Predicate::False => PredicateNode::False,
+            Predicate::BoolArg(a) => PredicateNode::BoolArg { name: a.clone() },
+            Predicate::LowerThan(v1, v2) => PredicateNode::Lt { left: v1.into(), right: v2.into() },
+            Predicate::LowerEqual(v1, v2) => PredicateNode::Le { left: v1.into(), right: v2.into() },
+            Predicate::GreaterThan(v1, v2) => PredicateNode::Gt { left: v1.into(), right: v2.into() },
+            Predicate::GreaterEqual(v1, v2) => PredicateNode::Ge { left: v1.into(), right: v2.into() },
+            Predicate::Equal(v1, v2) => PredicateNode::Eq { left: v1.into(), right: v2.into() },
+            Predicate::In(v, set) => PredicateNode::In { value: v.into(), set: set.clone() },
+            Predicate::NotIn(v, set) => PredicateNode::NotIn { value: v.into(), set: set.clone() },
+            Predicate::Not(p) => PredicateNode::Not { operand: boxed(p) },
+            Predicate::And(p1, p2) => PredicateNode::And { left: boxed(p1), right: boxed(p2) },
+            Predicate::Or(p1, p2) => PredicateNode::Or { left: boxed(p1), right: boxed(p2) },
+            Predicate::Implies(p1, p2) => PredicateNode::Implies { left: boxed(p1), right: boxed(p2) },
+            Predicate::Iff(p1, p2) => PredicateNode::Iff { left: boxed(p1), right: boxed(p2) },
+            Predicate::Xor(p1, p2) => PredicateNode::Xor { left: boxed(p1), right: boxed(p2) }
+        }
+    }
+}
+
+
+
+impl<T: Num + PartialOrd> From<PredicateNode<T>> for Predicate<T> {
+    fn from(p: PredicateNode<T>) -> Self {
+        let boxed = |p: Box<PredicateNode<T>>| -> Box<Predicate<T>> {Box::new((*p).into())};
+
+        match p {
+            PredicateNode::True => Predicate::True,
+            PredicateNode::False => Predicate::False,
+            PredicateNode::BoolArg { name } => Predicate::BoolArg(name),
+            PredicateNode::Lt { left, right } => Predicate::LowerThan(left.into(), right.into()),
+            PredicateNode::Le { left, right } => Predicate::LowerEqual(left.into(), right.into()),
+            PredicateNode::Gt { left, right } => Predicate::GreaterThan(left.into(), right.into()),
+            PredicateNode::Ge { left, right } => Predicate::GreaterEqual(left.into(), right.into()),
+            PredicateNode::Eq { left, right } => Predicate::Equal(left.into(), right.into()),
+            PredicateNode::In { value, set } => Predicate::In(value.into(), set),
+            PredicateNode::NotIn { value, set } => Predicate::NotIn(value.into(), set),
+            PredicateNode::Not { operand } => Predicate::Not(boxed(operand)),
+            PredicateNode::And { left, right } => Predicate::And(boxed(left), boxed(right)),
+            PredicateNode::Or { left, right } => Predicate::Or(boxed(left), boxed(right)),
+            PredicateNode::Implies { left, right } => Predicate::Implies(boxed(left), boxed(right)),
+            PredicateNode::Iff { left, right } => Predicate::Iff(boxed(left), boxed(right)),
+            PredicateNode::Xor { left, right } => Predicate::Xor(boxed(left), boxed(right))
+        }
+    }
+}
+
+
+
+impl<T: Num + PartialOrd + Clone> From<&Value<T>> for ValueNode<T> {
+    fn from(v: &Value<T>) -> Self {
+        let boxed = |v: &Value<T>| Box::new(ValueNode::from(v));
+
+        match v {
+            Value::Arg(a) => ValueNode::Arg { name: a.clone() },
+            Value::Literal(x) => ValueNode::Literal { value: x.clone() },
+            Value::Add(v1, v2) => ValueNode::Add { left: boxed(v1), right: boxed(v2) },
+            Value::Sub(v1, v2) => ValueNode::Sub { left: boxed(v1), right: boxed(v2) },
+            Value::Mul(v1, v2) => ValueNode::Mul { left: boxed(v1), right: boxed(v2) },
+            Value::Neg(v) => ValueNode::Neg { operand: boxed(v) }
+        }
+    }
+}
+
+
+
+impl<T: Num + PartialOrd> From<ValueNode<T>> for Value<T> {
+    fn from(v: ValueNode<T>) -> Self {
+        let boxed = |v: Box<ValueNode<T>>| Box::new(Value::from(*v));
+
+        match v {
+            ValueNode::Arg { name } => Value::Arg(name),
+            ValueNode::Literal { value } => Value::Literal(value),
+            ValueNode::Add { left, right } => Value::Add(boxed(left), boxed(right)),
+            ValueNode::Sub { left, right } => Value::Sub(boxed(left), boxed(right)),
+            ValueNode::Mul { left, right } => Value::Mul(boxed(left), boxed(right)),
+            ValueNode::Neg { operand } => Value::Neg(boxed(operand))
+        }
+    }
+}
+
+
+
+
+#[derive(Serialize, Deserialize)]
+struct PredicateDocument<T> {
+    version: u32,
+    predicate: PredicateNode<T>
+}
+
+
+impl<T: Num + PartialOrd + Clone + Serialize> Serialize for Predicate<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PredicateDocument { version: FORMAT_VERSION, predicate: self.into() }.serialize(serializer)
+    }
+}
+
+
+impl<'de, T: Num + PartialOrd + Deserialize<'de>> Deserialize<'de> for Predicate<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = PredicateDocument::deserialize(deserializer)?;
+        check_version(document.version)?;
+        Ok(document.predicate.into())
+    }
+}
+
+
+
+#[derive(Serialize, Deserialize)]
+struct ValueDocument<T> {
+    version: u32,
+    value: ValueNode<T>
+}
+
+
+impl<T: Num + PartialOrd + Clone + Serialize> Serialize for Value<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ValueDocument { version: FORMAT_VERSION, value: self.into() }.serialize(serializer)
+    }
+}
+
+
+impl<'de, T: Num + PartialOrd + Deserialize<'de>> Deserialize<'de> for Value<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = ValueDocument::deserialize(deserializer)?;
+        check_version(document.version)?;
+        Ok(document.value.into())
+    }
+}
+
+
+
+
+#[derive(Serialize, Deserialize)]
+struct Bound<T> {
+    value: T,
+    inclusive: bool
+}
+
+
+#[derive(Serialize, Deserialize)]
+struct IntervalNode<T> {
+    lower: Option<Bound<T>>,
+    upper: Option<Bound<T>>
+}
+
+
+impl<T: Num + Display + Clone> From<&Interval<T>> for IntervalNode<T> {
+    fn from(i: &Interval<T>) -> Self {
+        IntervalNode {
+            lower: i.lower.clone().map(|value| Bound { value, inclusive: i.incl_lower }),
+            upper: i.greater.clone().map(|value| Bound { value, inclusive: i.incl_greater })
+        }
+    }
+}
+
+
+impl<T: Num + PartialOrd + Clone + Debug + Display> From<IntervalNode<T>> for Interval<T> {
+    fn from(i: IntervalNode<T>) -> Self {
+        let (lower, incl_lower) = i.lower.map_or((None, false), |b| (Some(b.value), b.inclusive));
+        let (greater, incl_greater) = i.upper.map_or((None, false), |b| (Some(b.value), b.inclusive));
+        Interval::new(lower, incl_lower, greater, incl_greater)
+    }
+}
+
+
+impl<T: Num + Display + Clone + Serialize> Serialize for Interval<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        IntervalNode::from(self).serialize(serializer)
+    }
+}
+
+
+impl<'de, T: Num + PartialOrd + Clone + Debug + Display + Deserialize<'de>> Deserialize<'de> for Interval<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(IntervalNode::deserialize(deserializer)?.into())
+    }
+}
+
+
+
+#[derive(Serialize, Deserialize)]
+struct DomainDocument<T> {
+    version: u32,
+    integer: bool,
+    intervals: Vec<IntervalNode<T>>
+}
+
+
+impl<T: Num + Display + Clone + Serialize> Serialize for Domain<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let intervals = self.parts.iter().map(IntervalNode::from).collect();
+        DomainDocument { version: FORMAT_VERSION, integer: self.integer, intervals }.serialize(serializer)
+    }
+}
+
+
+/// The intervals of a deserialized domain are normalized like the ones of any other [Domain]
+/// (for example, an integer domain only has included integer bounds).
+impl<'de, T: Num + Signed + PartialOrd + Clone + ToPrimitive + Debug + Display + Checked + Deserialize<'de>> Deserialize<'de> for Domain<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = DomainDocument::deserialize(deserializer)?;
+        check_version(document.version)?;
+
+        let parts = document.intervals.into_iter().map(Interval::from).collect();
+        Ok(Domain::from_parts(parts, document.integer).simplified())
+    }
+}
+
+
+
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ImplicationNode {
+    Total,
+    Partial { coverage: Option<f64> },
+    Inexistant
+}
+
+
+/// Partial implication of the version 1 of the format.
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PartialV1 {
+    Partial
+}
+
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ImplicationDocument {
+    Current(ImplicationNode),
+    V1(PartialV1)
+}
+
+
+impl Serialize for Implication {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let node = match *self {
+            Implication::Total => ImplicationNode::Total,
+            Implication::Partial { coverage } => ImplicationNode::Partial { coverage },
+            Implication::Inexistant => ImplicationNode::Inexistant
+        };
+        node.serialize(serializer)
+    }
+}
+
+
+impl<'de> Deserialize<'de> for Implication {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match ImplicationDocument::deserialize(deserializer)? {
+            ImplicationDocument::Current(ImplicationNode::Total) => Implication::Total,
+            ImplicationDocument::Current(ImplicationNode::Partial { coverage }) => Implication::Partial { coverage },
+            ImplicationDocument::Current(ImplicationNode::Inexistant) => Implication::Inexistant,
+            ImplicationDocument::V1(PartialV1::Partial) => Implication::Partial { coverage: None }
+        })
+    }
+}
@@ -0,0 +1,573 @@
+//! Conversion of predicates from and to SMT-LIB 2 scripts, to be checked by external solvers.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use num::Num;
+
+use crate::arithmetic::Checked;
+use crate::assignment::Assignment;
+use crate::domain::is_integer;
+use crate::error::{ParseError, Span};
+use crate::normal::{conjunction, disjunction};
+use crate::parser::parse_literal;
+use crate::predicate::{Predicate, Value};
+use crate::schema::{Schema, Type};
+
+
+
+// symbols of SMT-LIB that can not name an argument without being quoted
+const RESERVED: [&str; 37] = [
+    "!", "_", "as", "BINARY", "DECIMAL", "exists", "forall", "HEXADECIMAL", "let", "match", "NUMERAL", "par", "STRING",
+    "true", "false", "not", "and", "or", "=>", "xor", "ite", "=", "distinct", "<", "<=", ">", ">=", "+", "-", "*", "/",
+    "div", "mod", "abs", "to_real", "to_int", "is_int"
+];
+
+// characters that can be used in a symbol without quoting it, in addition to letters and digits
+const SYMBOL_CHARS: &str = "~!@$%^&*_-+=<>.?/";
+
+// commands that do not change the asserted predicate
+const IGNORED_COMMANDS: [&str; 9] = ["set-logic", "set-info", "set-option", "check-sat", "get-model", "get-info", "get-option", "get-value", "exit"];
+
+// other commands, which are not supported
+const COMMANDS: [&str; 20] = [
+    "declare-const", "declare-fun", "assert", "define-fun", "define-fun-rec", "define-funs-rec", "define-sort", "declare-sort",
+    "declare-datatype", "declare-datatypes", "push", "pop", "reset", "reset-assertions", "check-sat-assuming",
+    "get-assertions", "get-assignment", "get-proof", "get-unsat-core", "echo"
+];
+
+// operators of boolean terms, except `ite`
+const BOOLEAN_OPS: [&str; 11] = ["not", "and", "or", "=>", "xor", "=", "distinct", "<", "<=", ">", ">="];
+
+
+
+/// Return the argument name as a SMT-LIB symbol, quoted if needed.
+fn symbol(name: &str) -> String {
+    let simple = name.chars().all(|c| c.is_ascii_alphanumeric() || SYMBOL_CHARS.contains(c))
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && !RESERVED.contains(&name);
+
+    if simple {name.to_string()} else {format!("|{name}|")}
+}
+
+
+/// Return the literal as a SMT-LIB term, written as a decimal if the arguments are reals.
+fn literal<T: Display>(x: &T, real: bool) -> String {
+    let txt = x.to_string();
+    let (negative, txt) = match txt.strip_prefix('-') {
+        Some(t) => (true, t),
+        None => (false, txt.as_str())
+    };
+
+    let number = |n: &str| if real && !n.contains('.') {format!("{n}.0")} else {n.to_string()};
+    let term = match txt.split_once('/') {
+        Some((n, d)) => format!("(/ {} {})", number(n), number(d)),
+        None => number(txt)
+    };
+
+    if negative {format!("(- {term})")} else {term}
+}
+
+
+fn write_value<T: Num + PartialOrd + Display>(v: &Value<T>, real: bool) -> String {
+    match v {
+        Value::Arg(a) => symbol(a),
+        Value::Literal(x) => literal(x, real),
+        Value::Add(v1, v2) => format!("(+ {} {})", write_value(v1, real), write_value(v2, real)),
+        Value::Sub(v1, v2) => format!("(- {} {})", write_value(v1, real), write_value(v2, real)),
+        Value::Mul(v1, v2) => format!("(* {} {})", write_value(v1, real), write_value(v2, real)),
+        Value::Neg(v) => format!("(- {})", write_value(v, real))
+    }
+}
+
+
+/// Return the term true when the value is one of the elements of the set, SMT-LIB having no sets.
+fn membership<T: Num + PartialOrd + Display>(v: &Value<T>, set: &[T], real: bool) -> String {
+    let v = write_value(v, real);
+    let equalities: Vec<String> = set.iter().map(|x| format!("(= {v} {})", literal(x, real))).collect();
+
+    match equalities.len() {
+        0 => "false".to_string(),
+        1 => equalities[0].clone(),
+        _ => format!("(or {})", equalities.join(" "))
+    }
+}
+
+
+fn write_predicate<T: Num + PartialOrd + Display>(p: &Predicate<T>, real: bool) -> String {
+    let comparison = |op: &str, v1: &Value<T>, v2: &Value<T>| format!("({op} {} {})", write_value(v1, real), write_value(v2, real));
+
+    match p {
+        Predicate::True => "true".to_string(),
+        Predicate::False => "false".to_string(),
+        Predicate::BoolArg(a) => symbol(a),
+        Predicate::LowerThan(v1, v2) => comparison("<", v1, v2),
+        Predicate::LowerEqual(v1, v2) => comparison("<=", v1, v2),
+        Predicate::GreaterThan(v1, v2) => comparison(">", v1, v2),
+        Predicate::GreaterEqual(v1, v2) => comparison(">=", v1, v2),
+        Predicate::Equal(v1, v2) => comparison("=", v1, v2),
+        Predicate::In(v, set) => membership(v, set, real),
+        Predicate::NotIn(v, set) => format!("(not {})", membership(v, set, real)),
+        Predicate::Not(p) => format!("(not {})", write_predicate(p, real)),
+        Predicate::And(p1, p2) => format!("(and {} {})", write_predicate(p1, real), write_predicate(p2, real)),
+        Predicate::Or(p1, p2) => format!("(or {} {})", write_predicate(p1, real), write_predicate(p2, real)),
+        Predicate::Implies(p1, p2) => format!("(=> {} {})", write_predicate(p1, real), write_predicate(p2, real)),
+        Predicate::Iff(p1, p2) => format!("(= {} {})", write_predicate(p1, real), write_predicate(p2, real)),
+        Predicate::Xor(p1, p2) => format!("(xor {} {})", write_predicate(p1, real), write_predicate(p2, real))
+    }
+}
+
+
+/// Add the boolean arguments and the numerical arguments of the predicate to the sets.
+fn collect_arguments<T: Num + PartialOrd + Clone>(p: &Predicate<T>, bools: &mut BTreeSet<String>, numbers: &mut BTreeSet<String>) {
+    match p {
+        Predicate::True | Predicate::False => (),
+        Predicate::BoolArg(a) => {bools.insert(a.clone());},
+        Predicate::LowerThan(v1, v2) | Predicate::LowerEqual(v1, v2) | Predicate::GreaterThan(v1, v2)
+        | Predicate::GreaterEqual(v1, v2) | Predicate::Equal(v1, v2) => {
+            numbers.extend(v1.get_arguments());
+            numbers.extend(v2.get_arguments());
+        },
+        Predicate::In(v, _) | Predicate::NotIn(v, _) => numbers.extend(v.get_arguments()),
+        Predicate::Not(p) => collect_arguments(p, bools, numbers),
+        Predicate::And(p1, p2) | Predicate::Or(p1, p2) | Predicate::Implies(p1, p2) | Predicate::Iff(p1, p2) | Predicate::Xor(p1, p2) => {
+            collect_arguments(p1, bools, numbers);
+            collect_arguments(p2, bools, numbers);
+        }
+    }
+}
+
+
+/// Return the SMT-LIB script declaring the arguments of the predicate and asserting it.
+fn script<T: Num + PartialOrd + Clone + Display>(p: &Predicate<T>) -> String {
+    let real = !is_integer::<T>();
+    let (mut bools, mut numbers) = (BTreeSet::new(), BTreeSet::new());
+    collect_arguments(p, &mut bools, &mut numbers);
+
+    let mut res = format!("(set-logic {})\n", if real {"QF_LRA"} else {"QF_LIA"});
+    for a in bools {
+        res += &format!("(declare-const {} Bool)\n", symbol(&a));
+    }
+    for a in numbers {
+        res += &format!("(declare-const {} {})\n", symbol(&a), if real {"Real"} else {"Int"});
+    }
+    res + &format!("(assert {})\n(check-sat)\n", write_predicate(p, real))
+}
+
+
+
+
+impl<T: Num + PartialOrd + Clone + Display> Predicate<T> {
+    /// Return a SMT-LIB 2 script asserting the predicate, which is satisfiable if the predicate is.
+    /// The logic is `QF_LIA` if `T` is an integer type, `QF_LRA` otherwise. The literals must be finite.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let p = Predicate::from("(x > 5) && !(2 * y == -1) || enabled").unwrap();
+    /// assert_eq!(p.to_smtlib(), "(set-logic QF_LRA)
+    /// (declare-const enabled Bool)
+    /// (declare-const x Real)
+    /// (declare-const y Real)
+    /// (assert (or (and (> x 5.0) (not (= (* 2.0 y) (- 1.0)))) enabled))
+    /// (check-sat)
+    /// ");
+    /// ```
+    pub fn to_smtlib(&self) -> String {
+        script(self)
+    }
+
+
+    /// Return a SMT-LIB 2 script asserting `self ∧ ¬other`: it is unsatisfiable if the implication
+    /// of `other` by `self` is total (see [Predicate::implies]), and a model of it is a counterexample otherwise.
+    pub fn implication_to_smtlib(&self, other: &Predicate<T>) -> String {
+        let query = Predicate::And(Box::new(self.clone()), Box::new(Predicate::Not(Box::new(other.clone()))));
+        script(&query)
+    }
+}
+
+
+
+
+/// Expression of a SMT-LIB script
+enum Sexp {
+    /// A symbol, a number, a keyword or a string
+    Atom(String, Span),
+
+    /// A symbol written between `|`, which can not be a number or an operator
+    Quoted(String, Span),
+
+    List(Vec<Sexp>, Span)
+}
+
+
+impl Sexp {
+    fn span(&self) -> &Span {
+        match self {
+            Sexp::Atom(_, s) | Sexp::Quoted(_, s) | Sexp::List(_, s) => s
+        }
+    }
+}
+
+
+
+/// Read the expressions of a SMT-LIB script.
+fn read_sexps(txt: &str) -> Result<Vec<Sexp>, ParseError> {
+    let mut stack: Vec<(Vec<Sexp>, usize)> = vec![(vec![], 0)];    // lists being read, with their start
+    let mut chars = txt.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            ';' => while chars.next_if(|(_, c)| *c != '\n').is_some() {},
+            '(' => stack.push((vec![], i)),
+            ')' => {
+                if stack.len() == 1 {return Err(ParseError::UnbalancedParenthesis(Span::new(txt, i, i + 1)))}
+                let (items, start) = stack.pop().unwrap();
+                stack.last_mut().unwrap().0.push(Sexp::List(items, Span::new(txt, start, i + 1)));
+            },
+            '|' => {
+                let end = match txt[i + 1..].find('|') {
+                    Some(j) => i + 1 + j,
+                    None => return Err(ParseError::UnexpectedToken(txt[i..].to_string(), Span::new(txt, i, txt.len())))
+                };
+                while chars.next_if(|(j, _)| *j <= end).is_some() {}
+                stack.last_mut().unwrap().0.push(Sexp::Quoted(txt[i + 1..end].to_string(), Span::new(txt, i, end + 1)));
+            },
+            '"' => {
+                // a string ends with a quote not followed by another one (`""` being an escaped quote)
+                let mut end = None;
+                while let Some((j, c)) = chars.next() {
+                    if c == '"' && chars.next_if(|(_, c)| *c == '"').is_none() {
+                        end = Some(j + 1);
+                        break;
+                    }
+                }
+                let end = end.ok_or_else(|| ParseError::UnexpectedToken(txt[i..].to_string(), Span::new(txt, i, txt.len())))?;
+                stack.last_mut().unwrap().0.push(Sexp::Atom(txt[i..end].to_string(), Span::new(txt, i, end)));
+            },
+            _ if c.is_whitespace() => (),
+            _ => {
+                let mut end = i + c.len_utf8();
+                while let Some((j, c)) = chars.next_if(|(_, c)| !c.is_whitespace() && !"();|\"".contains(*c)) {
+                    end = j + c.len_utf8();
+                }
+                stack.last_mut().unwrap().0.push(Sexp::Atom(txt[i..end].to_string(), Span::new(txt, i, end)));
+            }
+        }
+    }
+
+    if stack.len() > 1 {
+        let start = stack.last().unwrap().1;
+        return Err(ParseError::UnbalancedParenthesis(Span::new(txt, start, start + 1)))
+    }
+
+    Ok(stack.pop().unwrap().0)
+}
+
+
+
+/// Reader of the terms of a SMT-LIB script, knowing which arguments are declared as booleans or numbers.
+#[derive(Default)]
+struct Reader {
+    bools: HashMap<String, bool>,
+    integers: HashSet<String>
+}
+
+
+
+impl Reader {
+    /// Declare an argument with its sort, which must be `Bool`, `Int` or `Real`
+    /// (`Real` being rejected if `T` is an integer type).
+    fn declare<T: Num>(&mut self, name: &Sexp, sort: &Sexp) -> Result<(), ParseError> {
+        let name = match name {
+            Sexp::Atom(a, _) | Sexp::Quoted(a, _) => a.clone(),
+            Sexp::List(_, s) => return Err(ParseError::UnexpectedToken(String::new(), *s))
+        };
+
+        match sort {
+            Sexp::Atom(s, _) if s == "Bool" => self.bools.insert(name, true),
+            Sexp::Atom(s, _) if s == "Int" => {
+                self.integers.insert(name.clone());
+                self.bools.insert(name, false)
+            },
+            Sexp::Atom(s, _) if s == "Real" && !is_integer::<T>() => self.bools.insert(name, false),
+            Sexp::Atom(s, span) => return Err(ParseError::UnexpectedToken(s.clone(), *span)),
+            s => return Err(ParseError::UnexpectedToken(String::new(), *s.span()))
+        };
+        Ok(())
+    }
+
+
+    /// Return the schema of the declared arguments.
+    fn schema<T>(&self) -> Schema<T> {
+        let mut schema = Schema::new();
+        for (a, b) in &self.bools {
+            let t = if *b {Type::Bool} else if self.integers.contains(a) {Type::Int} else {Type::Real};
+            schema.declare(a, t);
+        }
+        schema
+    }
+
+
+    /// Return the operator applied by a list and its operands.
+    fn application<'a>(&self, items: &'a [Sexp], span: &Span) -> Result<(&'a str, &'a [Sexp]), ParseError> {
+        match items.split_first() {
+            Some((Sexp::Atom(op, _), operands)) => Ok((op, operands)),
+            Some((e, _)) => Err(ParseError::UnexpectedToken(String::new(), *e.span())),
+            None => Err(ParseError::UnexpectedToken("()".to_string(), *span))
+        }
+    }
+
+
+    /// Return true if the expression is a boolean term.
+    fn is_boolean(&self, e: &Sexp) -> bool {
+        match e {
+            Sexp::Atom(a, _) if a == "true" || a == "false" => true,
+            Sexp::Atom(a, _) | Sexp::Quoted(a, _) => self.bools.get(a) == Some(&true),
+            Sexp::List(items, _) => match items.first() {
+                Some(Sexp::Atom(op, _)) if op == "ite" => items.get(2).is_some_and(|e| self.is_boolean(e)),
+                Some(Sexp::Atom(op, _)) => BOOLEAN_OPS.contains(&op.as_str()),
+                _ => false
+            }
+        }
+    }
+
+
+    /// Return the predicates applying the comparison to each pair of consecutive operands.
+    fn chain<T, F>(&self, operands: &[Sexp], op: &str, span: &Span, compare: F) -> Result<Predicate<T>, ParseError>
+    where T: Num + PartialOrd + Clone + FromStr + Display + Checked, F: Fn(Value<T>, Value<T>) -> Predicate<T> {
+        if operands.len() < 2 {return Err(ParseError::MissingOperand(op.to_string(), *span))}
+
+        let values = operands.iter().map(|e| self.value(e)).collect::<Result<Vec<_>, _>>()?;
+        Ok(conjunction(values.windows(2).map(|w| compare(w[0].clone(), w[1].clone())).collect()))
+    }
+
+
+    /// Return the predicate of a boolean term.
+    fn predicate<T: Num + PartialOrd + Clone + FromStr + Display + Checked>(&self, e: &Sexp) -> Result<Predicate<T>, ParseError> {
+        let (items, span) = match e {
+            Sexp::Atom(a, _) if a == "true" => return Ok(Predicate::True),
+            Sexp::Atom(a, _) if a == "false" => return Ok(Predicate::False),
+            Sexp::Atom(a, span) if a.starts_with(|c: char| c.is_ascii_digit() || c == '"') => {
+                return Err(ParseError::UnexpectedToken(a.clone(), *span))
+            },
+            Sexp::Atom(a, span) | Sexp::Quoted(a, span) => {
+                if self.bools.get(a) == Some(&false) {return Err(ParseError::WrongType(a.clone(), *span))}
+                return Ok(Predicate::BoolArg(a.clone()))
+            },
+            Sexp::List(items, span) => (items, span)
+        };
+
+        let (op, operands) = self.application(items, span)?;
+        let predicates = || operands.iter().map(|e| self.predicate(e)).collect::<Result<Vec<_>, _>>();
+        let not = |p: Predicate<T>| Predicate::Not(Box::new(p));
+        let iff = |p1: Predicate<T>, p2: Predicate<T>| Predicate::Iff(Box::new(p1), Box::new(p2));
+        let xor = |p1: Predicate<T>, p2: Predicate<T>| Predicate::Xor(Box::new(p1), Box::new(p2));
+        let arity = |n: usize| {
+            if operands.len() == n {Ok(())}
+            else {Err(ParseError::MissingOperand(op.to_string(), *span))}
+        };
+
+        match op {
+            "not" => {
+                arity(1)?;
+                Ok(not(self.predicate(&operands[0])?))
+            },
+            "and" => Ok(conjunction(predicates()?)),
+            "or" => Ok(disjunction(predicates()?)),
+
+            // `=>` is right-associative, `xor` is left-associative
+            "=>" => {
+                let mut predicates = predicates()?;
+                let last = predicates.pop().ok_or_else(|| ParseError::MissingOperand(op.to_string(), *span))?;
+                Ok(predicates.into_iter().rev().fold(last, |p2, p1| Predicate::Implies(Box::new(p1), Box::new(p2))))
+            },
+            "xor" => {
+                let predicates = predicates()?;
+                if predicates.len() < 2 {return Err(ParseError::MissingOperand(op.to_string(), *span))}
+                Ok(predicates.into_iter().reduce(xor).unwrap())
+            },
+            "ite" => {
+                arity(3)?;
+                let (c, p1, p2) = (self.predicate(&operands[0])?, self.predicate(&operands[1])?, self.predicate(&operands[2])?);
+                Ok(disjunction(vec![conjunction(vec![c.clone(), p1]), conjunction(vec![not(c), p2])]))
+            },
+
+            "=" if operands.iter().any(|e| self.is_boolean(e)) => {
+                let predicates = predicates()?;
+                if predicates.len() < 2 {return Err(ParseError::MissingOperand(op.to_string(), *span))}
+                Ok(conjunction(predicates.windows(2).map(|w| iff(w[0].clone(), w[1].clone())).collect()))
+            },
+            "=" => self.chain(operands, op, span, Predicate::Equal),
+            "<" => self.chain(operands, op, span, Predicate::LowerThan),
+            "<=" => self.chain(operands, op, span, Predicate::LowerEqual),
+            ">" => self.chain(operands, op, span, Predicate::GreaterThan),
+            ">=" => self.chain(operands, op, span, Predicate::GreaterEqual),
+
+            // every pair of operands is different
+            "distinct" if operands.iter().any(|e| self.is_boolean(e)) => {
+                let predicates = predicates()?;
+                if predicates.len() != 2 {return Err(ParseError::MissingOperand(op.to_string(), *span))}
+                Ok(xor(predicates[0].clone(), predicates[1].clone()))
+            },
+            "distinct" => {
+                if operands.len() < 2 {return Err(ParseError::MissingOperand(op.to_string(), *span))}
+                let values = operands.iter().map(|e| self.value(e)).collect::<Result<Vec<_>, _>>()?;
+                let mut res = vec![];
+                for (i, v1) in values.iter().enumerate() {
+                    for v2 in &values[i + 1..] {
+                        res.push(not(Predicate::Equal(v1.clone(), v2.clone())));
+                    }
+                }
+                Ok(conjunction(res))
+            },
+
+            _ => Err(ParseError::UnknownOperator(op.to_string(), *span))
+        }
+    }
+
+
+    /// Return the value of a numerical term.
+    fn value<T: Num + PartialOrd + Clone + FromStr + Display + Checked>(&self, e: &Sexp) -> Result<Value<T>, ParseError> {
+        let (items, span) = match e {
+            Sexp::Atom(a, span) if a.starts_with(|c: char| c.is_ascii_digit()) => {
+                return match parse_literal(a) {
+                    Some(x) => Ok(Value::Literal(x)),
+                    None => Err(ParseError::UnexpectedToken(a.clone(), *span))
+                }
+            },
+            Sexp::Atom(a, span) if a == "true" || a == "false" || a.starts_with('"') => {
+                return Err(ParseError::UnexpectedToken(a.clone(), *span))
+            },
+            Sexp::Atom(a, span) | Sexp::Quoted(a, span) => {
+                if self.bools.get(a) == Some(&true) {return Err(ParseError::WrongType(a.clone(), *span))}
+                return Ok(Value::Arg(a.clone()))
+            },
+            Sexp::List(items, span) => (items, span)
+        };
+
+        let (op, operands) = self.application(items, span)?;
+        let values = operands.iter().map(|e| self.value(e)).collect::<Result<Vec<Value<T>>, _>>()?;
+        let missing = || ParseError::MissingOperand(op.to_string(), *span);
+
+        match op {
+            "+" => values.into_iter().reduce(|v1, v2| Value::Add(Box::new(v1), Box::new(v2))).ok_or_else(missing),
+
+            // a negated literal is a negative literal
+            "-" if values.len() == 1 => match values.into_iter().next().unwrap() {
+                Value::Literal(x) => Ok(Value::Literal(T::zero() - x)),
+                v => Ok(Value::Neg(Box::new(v)))
+            },
+            "-" => values.into_iter().reduce(|v1, v2| Value::Sub(Box::new(v1), Box::new(v2))).ok_or_else(missing),
+
+            "*" => {
+                if !Value::is_linear_product(&values) {
+                    return Err(ParseError::NonLinearProduct(*span))
+                }
+                values.into_iter().reduce(|v1, v2| Value::Mul(Box::new(v1), Box::new(v2))).ok_or_else(missing)
+            },
+
+            // divisions by a constant are products by its inverse
+            "/" => {
+                if values.len() < 2 {return Err(missing())}
+                let mut values = values.into_iter();
+                let mut res = values.next().unwrap();
+                for (v, e) in values.zip(&operands[1..]) {
+                    if !v.get_arguments().is_empty() {return Err(ParseError::NonLinearProduct(*span))}
+                    let d = v.evaluate(&Assignment::new()).map_err(|_| ParseError::NotANumber(*e.span()))?;
+                    if d == T::zero() {return Err(ParseError::NotANumber(*e.span()))}
+
+                    // with integer types, the division must be exact
+                    res = match res {
+                        v if v.get_arguments().is_empty() => {
+                            let n = v.evaluate(&Assignment::new()).map_err(|_| ParseError::NotANumber(*span))?;
+                            let q = n.clone() / d.clone();
+                            if q.clone() * d != n {return Err(ParseError::UnknownOperator(op.to_string(), *span))}
+                            Value::Literal(q)
+                        },
+                        v => {
+                            let inverse = T::one() / d.clone();
+                            if inverse.clone() * d != T::one() {return Err(ParseError::UnknownOperator(op.to_string(), *span))}
+                            Value::Mul(Box::new(v), Box::new(Value::Literal(inverse)))
+                        }
+                    };
+                }
+                Ok(res)
+            },
+
+            "to_real" if values.len() == 1 => Ok(values.into_iter().next().unwrap()),
+
+            _ => Err(ParseError::UnknownOperator(op.to_string(), *span))
+        }
+    }
+}
+
+
+impl<T: Num + PartialOrd + Clone + FromStr + Display + Checked> Predicate<T> {
+    /// Return the predicate asserted by a SMT-LIB 2 script in the `QF_LRA` or `QF_LIA` logic (the conjunction of
+    /// its assertions), or by a single SMT-LIB term. Arguments declared as `Bool` are boolean arguments, like the
+    /// undeclared ones used where a boolean is expected.
+    ///
+    /// Boolean operators that have no equivalent in [Predicate] (like `=>` or `ite`) are replaced by equivalent predicates,
+    /// and chained comparisons like `(< 0 x 10)` by conjunctions.
+    ///
+    /// The predicate does not tell that the arguments declared as `Int` only have integer values:
+    /// use [Predicate::from_smtlib_with_schema] to keep their sorts. If `T` is an integer type,
+    /// arguments can not be declared as `Real`.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let script = "(declare-const x Real)
+    ///     (declare-const enabled Bool)
+    ///     (assert (< 0 x 10))
+    ///     (assert (=> enabled (>= (/ x 2) 1.5)))
+    ///     (check-sat)";
+    /// let p = Predicate::<f64>::from_smtlib(script).unwrap();
+    /// assert!(p.equivalent(&Predicate::from("(x > 0) && (x < 10) && (! enabled || (x >= 3))").unwrap()));
+    /// ```
+    pub fn from_smtlib(txt: &str) -> Result<Predicate<T>, ParseError> {
+        Predicate::from_smtlib_with_schema(txt).map(|(p, _)| p)
+    }
+
+
+    /// Same as [Predicate::from_smtlib], also returning the [Schema] of the declared arguments,
+    /// which types are the ones of their sorts.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::{Predicate, Implication};
+    ///
+    /// let (p, schema) = Predicate::<f64>::from_smtlib_with_schema("(declare-const x Int) (assert (> x 5))").unwrap();
+    /// assert_eq!(schema.implies(&p, &Predicate::from("x >= 6").unwrap()), Implication::Total);
+    /// assert_ne!(p.implies(&Predicate::from("x >= 6").unwrap()), Implication::Total);
+    /// ```
+    pub fn from_smtlib_with_schema(txt: &str) -> Result<(Predicate<T>, Schema<T>), ParseError> {
+        let mut reader = Reader::default();
+        let mut assertions = vec![];
+
+        for e in read_sexps(txt)? {
+            let (items, span) = match &e {
+                Sexp::List(items, span) => (items, span),
+                _ => return Err(ParseError::UnexpectedToken(String::new(), *e.span()))
+            };
+
+            let (command, operands) = reader.application(items, span)?;
+            match (command, operands) {
+                (c, _) if IGNORED_COMMANDS.contains(&c) => (),
+                ("declare-const", [name, sort]) => reader.declare::<T>(name, sort)?,
+                ("declare-fun", [name, Sexp::List(params, _), sort]) if params.is_empty() => reader.declare::<T>(name, sort)?,
+                ("assert", [term]) => assertions.push(reader.predicate(term)?),
+                (c, _) if COMMANDS.contains(&c) => return Err(ParseError::UnexpectedToken(c.to_string(), *span)),
+
+                // a single term
+                _ => assertions.push(reader.predicate(&e)?)
+            }
+        }
+
+        Ok((conjunction(assertions), reader.schema()))
+    }
+}
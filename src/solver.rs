@@ -0,0 +1,281 @@
+//! Decision procedure for predicates: search for values of the arguments verifying a set of predicates.
+//!
+//! The search explores the predicates like a tableau: conjunctions add their operands to the current branch,
+//! disjunctions split the branch in two. Each branch gathers boolean arguments and linear constraints,
+//! and is closed as soon as those become contradictory.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Debug};
+
+use num::{Num, Signed, ToPrimitive};
+
+use crate::arithmetic::Checked;
+use crate::assignment::Assignment;
+use crate::domain::Domain;
+use crate::linear::System;
+use crate::predicate::Predicate;
+use crate::schema::Schema;
+
+
+
+/// What is assumed to be true along a branch of the search.
+#[derive(Clone)]
+struct Branch<T: Num> {
+    bools: HashMap<String, bool>,
+    system: System<T>
+}
+
+
+
+/// Return the ranges given by the schema to the arguments of the predicates.
+fn get_ranges<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(predicates: &[(&Predicate<T>, bool)], schema: &Schema<T>) -> Vec<Predicate<T>> {
+    let mut args: Vec<String> = predicates.iter().flat_map(|(p, _)| p.get_arguments()).collect();
+    args.sort();
+    args.dedup();
+
+    args.iter().filter_map(|a| schema.get_range(a)).collect()
+}
+
+
+
+/// Return the branch the search starts from, where the arguments declared as integers by the schema are known to be.
+fn root<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(predicates: &[(&Predicate<T>, bool)], schema: &Schema<T>) -> Branch<T> {
+    let mut branch = Branch { bools: HashMap::new(), system: System::new() };
+
+    for a in predicates.iter().flat_map(|(p, _)| p.get_arguments()) {
+        if schema.get_type(&a).is_some_and(|t| t.is_integer()) {branch.system.set_integer(&a);}
+    }
+
+    branch
+}
+
+
+
+/// Return true if there are values of the arguments, allowed by the schema, for which each predicate has the given truth value.
+///
+/// Comparisons that are not linear cannot be reasoned about and are considered to have any truth value,
+/// so this function can only err on the side of satisfiability.
+pub fn is_satisfiable<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(predicates: &[(&Predicate<T>, bool)], schema: &Schema<T>) -> bool {
+    let ranges = get_ranges(predicates, schema);
+    let todo = predicates.iter().copied().chain(ranges.iter().map(|p| (p, true))).collect();
+
+    search(todo, root(predicates, schema), &[], &mut |_| true)
+}
+
+
+
+/// Return values of the arguments, allowed by the schema, for which each predicate has the given truth value,
+/// or None if there are none. Every argument used by the predicates is given a value.
+///
+/// As comparisons that are not linear are not reasoned about, the values might not be correct for them.
+/// The values can also be missing when the predicates are satisfiable, as they are searched among the real
+/// numbers: rounding them, or restricting them to integers, can leave an argument without a value.
+pub fn find_model<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(predicates: &[(&Predicate<T>, bool)], schema: &Schema<T>) -> Option<Assignment<T>> {
+    let ranges = get_ranges(predicates, schema);
+    let todo = predicates.iter().copied().chain(ranges.iter().map(|p| (p, true))).collect();
+    let mut res = None;
+
+    search(todo, root(predicates, schema), &[], &mut |b| {
+        let mut assignment = Assignment::new();
+        match b.system.solve() {
+            None => false,      // rounding, or the inexact elimination over integers, can prevent finding values: try another branch
+            Some(values) => {
+                for (a, x) in values {assignment.set_value(&a, x);}
+                for (a, x) in &b.bools {assignment.set_bool(a, *x);}
+                res = Some(assignment);
+                true
+            }
+        }
+    });
+
+    let mut res = res?;
+
+    // the arguments that are not constrained by the branch can have any value
+    for (p, _) in predicates {complete(p, &mut res);}
+
+    Some(res)
+}
+
+
+
+/// Return the domain of the values of the given argument for which each predicate can have the given truth value,
+/// i.e. for which there are values of the other arguments giving each predicate this truth value.
+/// Only the values allowed by the schema are considered.
+pub fn get_domain<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(predicates: &[(&Predicate<T>, bool)], arg_name: &str, schema: &Schema<T>) -> Domain<T> {
+    let ranges = get_ranges(predicates, schema);
+    let todo = predicates.iter().copied().chain(ranges.iter().map(|p| (p, true))).collect();
+    let mut res = Domain::_false();
+
+    // the domain is the union of the domains of every branch
+    search(todo, root(predicates, schema), &[arg_name], &mut |b| {
+        res = Domain::union(res.clone(), b.system.get_domain(arg_name));
+        false
+    });
+
+    res
+}
+
+
+
+/// Give a default value to the arguments of the predicate that do not have one in the assignment.
+fn complete<T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked>(p: &Predicate<T>, assignment: &mut Assignment<T>) {
+    match p {
+        Predicate::True | Predicate::False => (),
+
+        Predicate::BoolArg(a) => {
+            if assignment.get_bool(a).is_none() {assignment.set_bool(a, false);}
+        },
+
+        Predicate::Not(p) => complete(p, assignment),
+        Predicate::And(p1, p2) | Predicate::Or(p1, p2) | Predicate::Implies(p1, p2) | Predicate::Iff(p1, p2) | Predicate::Xor(p1, p2) => {
+            complete(p1, assignment);
+            complete(p2, assignment);
+        },
+
+        // comparisons
+        _ => for a in p.get_arguments() {
+            if assignment.get_value(&a).is_none() {assignment.set_value(&a, T::zero());}
+        }
+    }
+}
+
+
+
+/// Return the groups of predicates that do not share arguments, directly or through the constraints of the branch.
+fn independent<'a, T>(predicates: Vec<(&'a Predicate<T>, bool)>, branch: &Branch<T>) -> Vec<Vec<(&'a Predicate<T>, bool)>>
+where T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked
+{
+    // each argument is linked to another one of its group, the last one of the chain representing the group
+    let mut links: HashMap<String, String> = HashMap::new();
+    fn find(links: &HashMap<String, String>, a: &str) -> String {
+        let mut a = a;
+        while let Some(b) = links.get(a) {a = b;}
+        a.to_string()
+    }
+    fn link(links: &mut HashMap<String, String>, args: Vec<String>) {
+        let mut args = args.iter().map(|a| find(links, a)).collect::<Vec<_>>().into_iter();
+        if let Some(first) = args.next() {
+            for a in args {
+                if a != first {links.insert(a, first.clone());}
+            }
+        }
+    }
+
+    for args in branch.system.get_related_arguments() {link(&mut links, args);}
+    for (p, _) in &predicates {link(&mut links, p.get_arguments().into_iter().collect());}
+
+    let mut groups: Vec<(Option<String>, Vec<_>)> = vec![];
+    for (p, value) in predicates {
+        let group = p.get_arguments().iter().next().map(|a| find(&links, a));
+        match groups.iter_mut().find(|(g, _)| group.is_some() && *g == group) {
+            Some((_, ps)) => ps.push((p, value)),
+            None => groups.push((group, vec![(p, value)]))
+        }
+    }
+
+    groups.into_iter().map(|(_, ps)| ps).collect()
+}
+
+
+
+/// Extend the branch with the predicates of `todo` having the given truth value,
+/// and call `found` on each resulting branch that can be verified until it returns true.
+/// Return true if the search was stopped this way.
+///
+/// The predicates that do not split the branch are added first, in order, closing the branch as soon as its constraints
+/// are contradictory. The others are then split on separately for each group of them not sharing arguments:
+/// a single branch is needed for the groups which do not use the arguments of `focus`.
+fn search<T>(todo: Vec<(&Predicate<T>, bool)>, mut branch: Branch<T>, focus: &[&str], found: &mut dyn FnMut(&Branch<T>) -> bool) -> bool
+where T: Num + Signed + PartialOrd + Clone + ToPrimitive + Display + Debug + Checked
+{
+    let mut todo: VecDeque<_> = todo.into();
+    let mut splits = vec![];
+
+    while let Some((p, value)) = todo.pop_front() {
+        match p {
+            Predicate::True => if !value {return false},
+            Predicate::False => if value {return false},
+
+            Predicate::BoolArg(a) => {
+                if branch.bools.insert(a.clone(), value) == Some(!value) {return false}
+            },
+
+            Predicate::Not(p) => todo.push_front((p, !value)),
+
+            // a && b is true, or a || b is false: both operands have the same value
+            Predicate::And(p1, p2) | Predicate::Or(p1, p2) if value == matches!(p, Predicate::And(..)) => {
+                todo.push_front((p2, value));
+                todo.push_front((p1, value));
+            },
+
+            // a => b is false: a is true and b is false
+            Predicate::Implies(p1, p2) if !value => {
+                todo.push_front((p2, false));
+                todo.push_front((p1, true));
+            },
+
+            Predicate::And(..) | Predicate::Or(..) | Predicate::Implies(..) | Predicate::Iff(..) | Predicate::Xor(..) => splits.push((p, value)),
+
+            // comparisons
+            _ => match p.get_constraints(!value) {
+                None => (),     // not linear: nothing can be deduced
+                Some(cases) if cases.len() > 1 => splits.push((p, value)),
+
+                Some(cases) => match cases.into_iter().next() {
+                    None => return false,
+                    Some(case) => {
+                        for c in case {branch.system.add(c);}
+                        if !branch.system.is_feasible() {return false}
+                    }
+                }
+            }
+        }
+    }
+
+    if splits.is_empty() {return found(&branch)}
+
+    // the groups that do not matter to `focus` only need one branch, which the others extend
+    let mut groups = independent(splits, &branch);
+    let main = groups.iter()
+        .position(|g| g.iter().any(|(p, _)| focus.iter().any(|a| p.get_arguments().contains(*a))))
+        .unwrap_or(groups.len() - 1);
+    let mut splits = groups.remove(main);
+
+    for group in groups {
+        let mut first = None;
+        if !search(group, branch.clone(), &[], &mut |b| {first = Some(b.clone()); true}) {return false}
+        if let Some(b) = first {branch = b;}
+    }
+
+    let (p, value) = splits.remove(0);
+    let cases = match p {
+        // a && b is false, or a || b is true: one of the operands at least has this value
+        Predicate::And(p1, p2) | Predicate::Or(p1, p2) => vec![vec![(&**p1, value)], vec![(&**p2, value)]],
+
+        // a => b is true: a is false or b is true
+        Predicate::Implies(p1, p2) => vec![vec![(&**p1, false)], vec![(&**p2, true)]],
+
+        // a <=> b and a xor b: a is true and b has a given value, or a is false and b has the opposite one
+        Predicate::Iff(p1, p2) | Predicate::Xor(p1, p2) => {
+            let same = value == matches!(p, Predicate::Iff(..));
+            vec![vec![(&**p1, true), (&**p2, same)], vec![(&**p1, false), (&**p2, !same)]]
+        },
+
+        // comparisons with several cases, like `x != 5`
+        _ => {
+            for case in p.get_constraints(!value).unwrap_or_default() {
+                let mut b = branch.clone();
+                for c in case {b.system.add(c);}
+                if b.system.is_feasible() && search(splits.clone(), b, focus, found) {return true}
+            }
+            return false
+        }
+    };
+
+    for case in cases {
+        let todo = case.into_iter().chain(splits.iter().copied()).collect();
+        if search(todo, branch.clone(), focus, found) {return true}
+    }
+
+    false
+}
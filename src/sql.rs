@@ -0,0 +1,597 @@
+//! Conversion of predicates from and to the boolean expressions of SQL `WHERE` clauses.
+
+use std::str::FromStr;
+
+use num::Num;
+
+use crate::error::{ParseError, Span};
+use crate::normal::disjunction;
+use crate::parser::parse_literal;
+use crate::predicate::{Predicate, Value};
+
+
+
+const KEYWORDS: [&str; 7] = ["AND", "OR", "NOT", "BETWEEN", "IN", "TRUE", "FALSE"];
+const COMPARISONS: [&str; 7] = ["=", "<>", "!=", "<", "<=", ">", ">="];
+
+
+
+/// Syntax of the parameter placeholders of a SQL query, which replace its literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeholder {
+    /// `?`, used by SQLite or MySQL for example
+    QuestionMark,
+
+    /// `$1`, `$2`, etc. used by PostgreSQL for example
+    Numbered
+}
+
+
+
+/// Return the name of an argument as a quoted SQL identifier.
+fn identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+
+
+/// Writer of a SQL expression, which replaces the literals by placeholders.
+struct Writer<T> {
+    placeholder: Placeholder,
+    params: Vec<T>
+}
+
+
+
+impl<T: Num + PartialOrd + Clone> Writer<T> {
+    /// Return the SQL value, with its precedence: operators with a higher precedence are applied first.
+    fn value(&mut self, v: &Value<T>) -> (String, u8) {
+        let (v1, op, v2, precedence) = match v {
+            Value::Arg(a) => return (identifier(a), 8),
+            Value::Literal(x) => {
+                self.params.push(x.clone());
+                let p = match self.placeholder {
+                    Placeholder::QuestionMark => "?".to_string(),
+                    Placeholder::Numbered => format!("${}", self.params.len())
+                };
+                return (p, 8)
+            },
+            // `--` would start a comment
+            Value::Neg(v) => return (format!("-{}", self.operand(v, 8)), 7),
+
+            Value::Add(v1, v2) => (v1, "+", v2, 5),
+            Value::Sub(v1, v2) => (v1, "-", v2, 5),
+            Value::Mul(v1, v2) => (v1, "*", v2, 6)
+        };
+
+        // `a - (b - c)` is not `a - b - c`
+        (format!("{} {op} {}", self.operand(v1, precedence), self.operand(v2, precedence + 1)), precedence)
+    }
+
+
+    /// Return the SQL value, between parentheses if its precedence is lower than the given one.
+    fn operand(&mut self, v: &Value<T>, precedence: u8) -> String {
+        let (txt, p) = self.value(v);
+        if p < precedence {format!("({txt})")} else {txt}
+    }
+
+
+    /// Return the SQL boolean expression, with its precedence.
+    fn predicate(&mut self, p: &Predicate<T>) -> (String, u8) {
+        let (v1, op, v2) = match p {
+            Predicate::True => return ("TRUE".to_string(), 8),
+            Predicate::False => return ("FALSE".to_string(), 8),
+            Predicate::BoolArg(a) => return (identifier(a), 8),
+
+            // SQL has no implication nor equivalence of conditions
+            Predicate::Implies(..) | Predicate::Iff(..) | Predicate::Xor(..) => return self.predicate(&p.desugared().unwrap()),
+
+            Predicate::Not(p) => match p.as_ref() {
+                Predicate::Equal(v1, v2) => (v1, "<>", v2),
+                p => return (format!("NOT {}", self.condition(p, 3)), 3)
+            },
+            Predicate::And(p1, p2) => return (format!("{} AND {}", self.condition(p1, 2), self.condition(p2, 3)), 2),
+            Predicate::Or(p1, p2) => {
+                // AND is applied before OR, but the parentheses are kept between them to be readable
+                let precedence = |p: &Predicate<T>| if let Predicate::And(..) = p {3} else {1};
+                return (format!("{} OR {}", self.condition(p1, precedence(p1)), self.condition(p2, precedence(p2).max(2))), 1)
+            },
+
+            Predicate::LowerThan(v1, v2) => (v1, "<", v2),
+            Predicate::LowerEqual(v1, v2) => (v1, "<=", v2),
+            Predicate::GreaterThan(v1, v2) => (v1, ">", v2),
+            Predicate::GreaterEqual(v1, v2) => (v1, ">=", v2),
+            Predicate::Equal(v1, v2) => (v1, "=", v2),
+
+            // a list of values can not be empty
+            Predicate::In(_, set) if set.is_empty() => return ("FALSE".to_string(), 8),
+            Predicate::NotIn(_, set) if set.is_empty() => return ("TRUE".to_string(), 8),
+            Predicate::In(v, set) | Predicate::NotIn(v, set) => {
+                let op = if let Predicate::In(..) = p {"IN"} else {"NOT IN"};
+                let v = self.value(v).0;
+                let set: Vec<String> = set.iter().map(|x| self.value(&Value::Literal(x.clone())).0).collect();
+                return (format!("{v} {op} ({})", set.join(", ")), 4)
+            }
+        };
+
+        // values always have a higher precedence than comparisons
+        (format!("{} {op} {}", self.value(v1).0, self.value(v2).0), 4)
+    }
+
+
+    /// Return the SQL boolean expression, between parentheses if its precedence is lower than the given one.
+    fn condition(&mut self, p: &Predicate<T>, precedence: u8) -> String {
+        let (txt, p) = self.predicate(p);
+        if p < precedence {format!("({txt})")} else {txt}
+    }
+}
+
+
+
+
+impl<T: Num + PartialOrd + Clone> Predicate<T> {
+    /// Return the predicate as the condition of a SQL `WHERE` clause, with the arguments as quoted identifiers
+    /// and the literals replaced by placeholders, and the values of the literals in the order of their placeholders.
+    /// Boolean arguments are boolean columns.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::{Predicate, Placeholder};
+    ///
+    /// let p = Predicate::from("(age >= 18) && !(country == 33) || premium").unwrap();
+    /// let (sql, params) = p.to_sql(Placeholder::Numbered);
+    /// assert_eq!(sql, "(\"age\" >= $1 AND \"country\" <> $2) OR \"premium\"");
+    /// assert_eq!(params, vec![18.0, 33.0]);
+    /// ```
+    pub fn to_sql(&self, placeholder: Placeholder) -> (String, Vec<T>) {
+        let mut writer = Writer { placeholder, params: vec![] };
+        let (sql, _) = writer.predicate(self);
+        (sql, writer.params)
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Keyword, in uppercase
+    Keyword(String),
+    Identifier(String),
+    Number(String),
+
+    /// Placeholder of a parameter, with its number if it has one
+    Placeholder(Option<usize>),
+
+    Symbol(String)
+}
+
+
+
+/// Convert a SQL expression into tokens, each with its location in the string.
+fn tokenize(txt: &str) -> Result<Vec<(Token, Span)>, ParseError> {
+    let mut res = vec![];
+    let mut chars = txt.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let mut end = i + c.len_utf8();
+        let mut take_while = |chars: &mut std::iter::Peekable<std::str::CharIndices>, f: fn(char) -> bool| {
+            while let Some((j, c)) = chars.next_if(|(_, c)| f(*c)) {
+                end = j + c.len_utf8();
+            }
+            end
+        };
+
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+
+            '"' | '`' => {
+                // a quote is escaped by doubling it
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((j, d)) if d == c => {
+                            if chars.next_if(|(_, d)| *d == c).is_none() {
+                                end = j + 1;
+                                break
+                            }
+                            name.push(c);
+                        },
+                        Some((_, d)) => name.push(d),
+                        None => return Err(ParseError::UnexpectedToken(txt[i..].to_string(), Span::new(txt, i, txt.len())))
+                    }
+                }
+                Token::Identifier(name)
+            },
+
+            _ if c.is_alphabetic() || c == '_' => {
+                let end = take_while(&mut chars, |c| c.is_alphanumeric() || c == '_');
+                let word = &txt[i..end];
+                if KEYWORDS.contains(&word.to_uppercase().as_str()) {Token::Keyword(word.to_uppercase())}
+                else {Token::Identifier(word.to_string())}
+            },
+
+            _ if c.is_ascii_digit() || c == '.' => {
+                let end = take_while(&mut chars, |c| c.is_ascii_digit() || c == '.');
+                Token::Number(txt[i..end].to_string())
+            },
+
+            '?' => Token::Placeholder(None),
+            '$' => {
+                let end = take_while(&mut chars, |c| c.is_ascii_digit());
+                match txt[i + 1..end].parse::<usize>() {
+                    Ok(n) if n > 0 => Token::Placeholder(Some(n)),
+                    _ => return Err(ParseError::UnexpectedToken(txt[i..end].to_string(), Span::new(txt, i, end)))
+                }
+            },
+
+            '<' | '>' | '!' => {
+                if chars.next_if(|(_, d)| *d == '=' || (c == '<' && *d == '>')).is_some() {end += 1}
+                Token::Symbol(txt[i..end].to_string())
+            },
+            '=' | '+' | '-' | '*' | '(' | ')' | ',' => Token::Symbol(c.to_string()),
+
+            _ => return Err(ParseError::UnexpectedToken(c.to_string(), Span::new(txt, i, end)))
+        };
+
+        if token == Token::Symbol("!".to_string()) {
+            return Err(ParseError::UnknownOperator("!".to_string(), Span::new(txt, i, end)))
+        }
+        res.push((token, Span::new(txt, i, end)));
+    }
+
+    Ok(res)
+}
+
+
+
+
+/// Operand of an operator, either a value or a predicate, with its location
+enum Operand<T: Num + PartialOrd> {
+    Value(Value<T>),
+    Predicate(Predicate<T>)
+}
+
+
+
+/// Recursive descent parser of a SQL boolean expression.
+struct Reader<'a, T> {
+    txt: &'a str,
+    tokens: Vec<(Token, Span)>,
+    position: usize,
+
+    /// Values of the placeholders
+    params: &'a [T],
+    /// Number of `?` placeholders already read
+    unnumbered: usize
+}
+
+
+
+impl<T: Num + PartialOrd + Clone + FromStr> Reader<'_, T> {
+    /// Return the next token, without reading it.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(t, _)| t)
+    }
+
+
+    /// Return the location of the next token (or of the end of the string).
+    fn span(&self) -> Span {
+        match self.tokens.get(self.position) {
+            Some((_, s)) => *s,
+            None => Span::new(self.txt, self.txt.len(), self.txt.len())
+        }
+    }
+
+
+    /// Read the next token if it is the given keyword or symbol.
+    fn accept(&mut self, t: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Keyword(k) | Token::Symbol(k)) if k == t);
+        if found {self.position += 1}
+        found
+    }
+
+
+    /// Read the next token, which must be the given keyword or symbol.
+    fn expect(&mut self, t: &str) -> Result<(), ParseError> {
+        if self.accept(t) {Ok(())}
+        else {Err(self.unexpected())}
+    }
+
+
+    /// Return the error for the next token, which is not expected.
+    fn unexpected(&self) -> ParseError {
+        let span = self.span();
+        ParseError::UnexpectedToken(self.txt[span.start..span.end].to_string(), span)
+    }
+
+
+    /// Return the operand as a predicate. A column used where a predicate is expected is a boolean argument.
+    fn expect_predicate(&self, o: Operand<T>, span: Span) -> Result<Predicate<T>, ParseError> {
+        match o {
+            Operand::Predicate(p) => Ok(p),
+            Operand::Value(Value::Arg(a)) => Ok(Predicate::BoolArg(a)),
+            Operand::Value(_) => {
+                let end = self.tokens[self.position - 1].1.end;
+                Err(ParseError::UnexpectedToken(self.txt[span.start..end].to_string(), Span::new(self.txt, span.start, end)))
+            }
+        }
+    }
+
+
+    /// Read an expression of the form `a OR b OR ...`, or a value.
+    fn or(&mut self) -> Result<Operand<T>, ParseError> {
+        let span = self.span();
+        let first = self.and()?;
+        if !matches!(self.peek(), Some(Token::Keyword(k)) if k == "OR") {return Ok(first)}
+
+        let mut p = self.expect_predicate(first, span)?;
+        while self.accept("OR") {
+            let span = self.span();
+            let p2 = self.and()?;
+            p = Predicate::Or(Box::new(p), Box::new(self.expect_predicate(p2, span)?));
+        }
+        Ok(Operand::Predicate(p))
+    }
+
+
+    /// Read an expression of the form `a AND b AND ...`, or a value.
+    fn and(&mut self) -> Result<Operand<T>, ParseError> {
+        let span = self.span();
+        let first = self.not()?;
+        if !matches!(self.peek(), Some(Token::Keyword(k)) if k == "AND") {return Ok(first)}
+
+        let mut p = self.expect_predicate(first, span)?;
+        while self.accept("AND") {
+            let span = self.span();
+            let p2 = self.not()?;
+            p = Predicate::And(Box::new(p), Box::new(self.expect_predicate(p2, span)?));
+        }
+        Ok(Operand::Predicate(p))
+    }
+
+
+    /// Read an expression of the form `NOT a`, or a comparison.
+    fn not(&mut self) -> Result<Operand<T>, ParseError> {
+        if !self.accept("NOT") {return self.comparison()}
+
+        let span = self.span();
+        let o = self.not()?;
+        Ok(Operand::Predicate(Predicate::Not(Box::new(self.expect_predicate(o, span)?))))
+    }
+
+
+    /// Read a comparison between two values (including `BETWEEN` and `IN`), or a value.
+    fn comparison(&mut self) -> Result<Operand<T>, ParseError> {
+        let v = match self.sum()? {
+            Operand::Value(v) => v,
+            p => return Ok(p)
+        };
+
+        let (token, span) = match self.tokens.get(self.position) {
+            Some(t) => t.clone(),
+            None => return Ok(Operand::Value(v))
+        };
+
+        let op = match token {
+            Token::Symbol(op) if COMPARISONS.contains(&op.as_str()) => op,
+            Token::Keyword(k) if k == "NOT" || k == "BETWEEN" || k == "IN" => k,
+            _ => return Ok(Operand::Value(v))
+        };
+        self.position += 1;
+
+        let negated = op == "NOT";
+        let op = if negated {
+            match self.peek() {
+                Some(Token::Keyword(k)) if k == "BETWEEN" || k == "IN" => {
+                    let k = k.clone();
+                    self.position += 1;
+                    k
+                },
+                _ => return Err(ParseError::UnexpectedToken("NOT".to_string(), span))
+            }
+        } else {op};
+
+        let p = match op.as_str() {
+            "BETWEEN" => {
+                let lower = self.value()?;
+                self.expect("AND")?;
+                let greater = self.value()?;
+                Predicate::And(
+                    Box::new(Predicate::GreaterEqual(v.clone(), lower)),
+                    Box::new(Predicate::LowerEqual(v, greater))
+                )
+            },
+            "IN" => {
+                self.expect("(")?;
+                let mut values = vec![self.value()?];
+                while self.accept(",") {
+                    values.push(self.value()?);
+                }
+                self.expect(")")?;
+
+                // a list of literals is a set
+                let literals: Vec<T> = values.iter().filter_map(|x| match x {
+                    Value::Literal(x) => Some(x.clone()),
+                    _ => None
+                }).collect();
+                if literals.len() == values.len() {
+                    let p = if negated {Predicate::NotIn(v, literals)} else {Predicate::In(v, literals)};
+                    return Ok(Operand::Predicate(p))
+                }
+                disjunction(values.into_iter().map(|x| Predicate::Equal(v.clone(), x)).collect())
+            },
+            "=" => Predicate::Equal(v, self.value()?),
+            "<>" | "!=" => Predicate::Not(Box::new(Predicate::Equal(v, self.value()?))),
+            "<" => Predicate::LowerThan(v, self.value()?),
+            "<=" => Predicate::LowerEqual(v, self.value()?),
+            ">" => Predicate::GreaterThan(v, self.value()?),
+            ">=" => Predicate::GreaterEqual(v, self.value()?),
+            _ => return Err(ParseError::UnknownOperator(op, span))
+        };
+
+        Ok(Operand::Predicate(if negated {Predicate::Not(Box::new(p))} else {p}))
+    }
+
+
+    /// Read a value, which can not be a predicate.
+    fn value(&mut self) -> Result<Value<T>, ParseError> {
+        let span = self.span();
+        match self.sum()? {
+            Operand::Value(v) => Ok(v),
+            Operand::Predicate(_) => Err(ParseError::UnexpectedToken(self.txt[span.start..self.span().start].trim_end().to_string(), span))
+        }
+    }
+
+
+    /// Read a sum of products, or a predicate between parentheses.
+    fn sum(&mut self) -> Result<Operand<T>, ParseError> {
+        let mut v = match self.product()? {
+            Operand::Value(v) => v,
+            p => return Ok(p)
+        };
+
+        loop {
+            let op_span = self.span();
+            let sub = if self.accept("+") {false} else if self.accept("-") {true} else {break};
+            let v2 = match self.product()? {
+                Operand::Value(v2) => Box::new(v2),
+                Operand::Predicate(_) => return Err(ParseError::MissingOperand(if sub {"-"} else {"+"}.to_string(), op_span))
+            };
+            v = if sub {Value::Sub(Box::new(v), v2)} else {Value::Add(Box::new(v), v2)};
+        }
+
+        Ok(Operand::Value(v))
+    }
+
+
+    /// Read a product of a constant and a value, or a predicate between parentheses.
+    fn product(&mut self) -> Result<Operand<T>, ParseError> {
+        let start = self.span();
+        let mut v = match self.unary()? {
+            Operand::Value(v) => v,
+            p => return Ok(p)
+        };
+
+        loop {
+            let op_span = self.span();
+            if !self.accept("*") {break}
+            let v2 = match self.unary()? {
+                Operand::Value(v2) => v2,
+                Operand::Predicate(_) => return Err(ParseError::MissingOperand("*".to_string(), op_span))
+            };
+
+            if !Value::is_linear_product([&v, &v2]) {
+                let end = self.tokens[self.position - 1].1.end;
+                return Err(ParseError::NonLinearProduct(Span::new(self.txt, start.start, end)))
+            }
+            v = Value::Mul(Box::new(v), Box::new(v2));
+        }
+
+        Ok(Operand::Value(v))
+    }
+
+
+    /// Read a value with its sign, or a predicate between parentheses.
+    fn unary(&mut self) -> Result<Operand<T>, ParseError> {
+        let op_span = self.span();
+        let negated = if self.accept("-") {true} else {self.accept("+"); false};
+        if !negated {return self.primary()}
+
+        // a negated number is a negative literal
+        if let Some(Token::Number(n)) = self.peek() {
+            let n = format!("-{n}");
+            let span = self.span();
+            self.position += 1;
+            return self.literal(&n, span).map(Operand::Value)
+        }
+
+        match self.unary()? {
+            Operand::Value(v) => Ok(Operand::Value(Value::Neg(Box::new(v)))),
+            Operand::Predicate(_) => Err(ParseError::MissingOperand("-".to_string(), op_span))
+        }
+    }
+
+
+    /// Return the literal written in the string.
+    fn literal(&self, n: &str, span: Span) -> Result<Value<T>, ParseError> {
+        match parse_literal(n) {
+            Some(x) => Ok(Value::Literal(x)),
+            None => Err(ParseError::UnexpectedToken(n.to_string(), span))
+        }
+    }
+
+
+    /// Read a literal, a parameter, a column, a boolean constant or an expression between parentheses.
+    fn primary(&mut self) -> Result<Operand<T>, ParseError> {
+        let (token, span) = match self.tokens.get(self.position) {
+            Some(t) => t.clone(),
+            None => return Err(self.unexpected())
+        };
+        self.position += 1;
+
+        match token {
+            Token::Number(n) => self.literal(&n, span).map(Operand::Value),
+            Token::Identifier(a) => Ok(Operand::Value(Value::Arg(a))),
+            Token::Keyword(k) if k == "TRUE" => Ok(Operand::Predicate(Predicate::True)),
+            Token::Keyword(k) if k == "FALSE" => Ok(Operand::Predicate(Predicate::False)),
+
+            Token::Placeholder(n) => {
+                let i = n.map(|n| n - 1).unwrap_or(self.unnumbered);
+                if n.is_none() {self.unnumbered += 1}
+                match self.params.get(i) {
+                    Some(x) => Ok(Operand::Value(Value::Literal(x.clone()))),
+                    None => Err(ParseError::MissingOperand(self.txt[span.start..span.end].to_string(), span))
+                }
+            },
+
+            // a predicate, or a value (which can be compared afterwards)
+            Token::Symbol(s) if s == "(" => {
+                let operand = self.or()?;
+                match self.tokens.get(self.position) {
+                    Some((Token::Symbol(s), _)) if s == ")" => self.position += 1,
+                    None => return Err(ParseError::UnbalancedParenthesis(span)),
+                    _ => return Err(self.unexpected())
+                }
+                Ok(operand)
+            },
+
+            _ => {
+                self.position -= 1;
+                Err(self.unexpected())
+            }
+        }
+    }
+}
+
+
+
+
+impl<T: Num + PartialOrd + Clone + FromStr> Predicate<T> {
+    /// Return the predicate from the condition of a SQL `WHERE` clause, made of comparisons of linear expressions
+    /// (with `=`, `<>`, `!=`, `<`, `<=`, `>`, `>=`, `BETWEEN` and `IN`), boolean columns, `TRUE`, `FALSE`, `AND`, `OR` and `NOT`.
+    ///
+    /// The columns are the arguments of the predicate, and can be quoted with `"` or `` ` ``.
+    /// The placeholders `?` (taking the parameters in order) and `$1`, `$2`, etc. are replaced by the values of the parameters.
+    ///
+    /// # Example
+    /// ```
+    /// use predicatechecker::Predicate;
+    ///
+    /// let p = Predicate::from_sql("age BETWEEN ? AND 65 AND \"country\" NOT IN (33, $2)", &[18.0, 44.0]).unwrap();
+    /// let expected = Predicate::from("(age >= 18) && (age <= 65) && (country not in {33, 44})").unwrap();
+    /// assert_eq!(p, expected);
+    /// ```
+    pub fn from_sql(txt: &str, params: &[T]) -> Result<Predicate<T>, ParseError> {
+        let mut reader = Reader { txt, tokens: tokenize(txt)?, position: 0, params, unnumbered: 0 };
+        let span = reader.span();
+        let o = reader.or()?;
+        let p = reader.expect_predicate(o, span)?;
+
+        match reader.tokens.get(reader.position) {
+            None => Ok(p),
+            Some((Token::Symbol(s), span)) if s == ")" => Err(ParseError::UnbalancedParenthesis(*span)),
+            Some(_) => Err(reader.unexpected())
+        }
+    }
+}
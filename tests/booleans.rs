@@ -0,0 +1,43 @@
+//! Arguments used as predicates.
+
+mod common;
+
+use predicatechecker::{Predicate, Implication};
+use common::parse;
+
+
+
+fn flag(name: &str) -> Predicate<f64> {
+    Predicate::BoolArg(name.to_string())
+}
+
+
+
+#[test]
+fn identifiers_in_boolean_position() {
+    assert_eq!(parse("enabled"), flag("enabled"));
+    assert_eq!(parse("!enabled || (x > 0)"), Predicate::Or(Box::new(Predicate::Not(Box::new(flag("enabled")))), Box::new(parse("x > 0"))));
+    assert_eq!(parse("(a && b)"), Predicate::And(Box::new(flag("a")), Box::new(flag("b"))));
+}
+
+
+#[test]
+fn propositional_reasoning() {
+    assert_eq!(parse("a && (x > 1)").implies(&flag("a")), Implication::Total);
+    assert!(parse("a && !a").is_contradiction());
+    assert!(parse("a || !a").is_valid());
+    assert_eq!(parse("(a || b) && !a").implies(&flag("b")), Implication::Total);
+    assert_eq!(parse("a && b").implies(&parse("b || c")), Implication::Total);
+    assert_ne!(parse("a || b").implies(&flag("a")), Implication::Total);
+    assert!(parse("(a => b) && (b => c) && a && !c").is_contradiction());
+}
+
+
+#[test]
+fn mixed_with_comparisons() {
+    assert_eq!(parse("(a || (x > 5)) && (x < 0)").implies(&flag("a")), Implication::Total);
+    assert_eq!(parse("(a && (x > 5)) || (x > 10)").implies(&parse("x > 5")), Implication::Total);
+    assert_eq!(parse("(a && (x > 5)) || (!a && (x > 10))").implies(&parse("x > 5")), Implication::Total);
+    assert!(parse("(a => (x > 5)) && a && (x < 0)").is_contradiction());
+    assert_eq!(parse("(a => (x > 5)) && (x < 0)").implies(&parse("!a")), Implication::Total);
+}
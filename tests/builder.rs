@@ -0,0 +1,47 @@
+//! Predicates built with Rust expressions.
+
+use num::{BigRational, BigInt};
+use predicatechecker::{Predicate, Implication, Value, var, flag};
+
+
+
+#[test]
+fn operators() {
+    let p: Predicate<f64> = var("x").gt(5) & var("y").le(3) | !flag("enabled");
+    assert_eq!(p, Predicate::from("((x > 5) && (y <= 3)) || ! enabled").unwrap());
+
+    let p: Predicate<f64> = var("x").ge(-1.5) & !(var("x").lt(var("y")) | var("y").equals(0));
+    assert_eq!(p, Predicate::from("(x >= -1.5) && !((x < y) || (y == 0))").unwrap());
+
+    let p: Predicate<f64> = var("x").not_equals(2);
+    assert_eq!(p, Predicate::from("!(x == 2)").unwrap());
+}
+
+
+#[test]
+fn arithmetic() {
+    let p: Predicate<i64> = (var("x").value() * 2 - var("y") + 1).le(-var("z").value());
+    assert_eq!(p, Predicate::parse("x * 2 - y + 1 <= - z").unwrap());
+    assert_eq!(p.to_string(), "x * 2 - y + 1 <= - z");
+}
+
+
+#[test]
+fn other_types() {
+    let a: Predicate<i64> = var("x").gt(5);
+    assert_eq!(a.implies(&var("x").ge(6)), Implication::Total);
+
+    let ratio = |n: i64, d: i64| Value::Literal(BigRational::new(BigInt::from(n), BigInt::from(d)));
+    let a: Predicate<BigRational> = var("x").lt(ratio(1, 3)) & var("x").gt(ratio(1, 4));
+    assert_eq!(a, Predicate::parse("(x < 1/3) && (x > 1/4)").unwrap());
+}
+
+
+#[test]
+fn literals_of_primitive_types() {
+    let p: Predicate<f64> = var("x").lt(5_u32) & var("x").gt(-2_i32) & var("y").equals(0.5_f32);
+    assert_eq!(p, Predicate::from("(x < 5) && (x > -2) && (y == 0.5)").unwrap());
+
+    let p: Predicate<i64> = var("x").lt(5) & var("x").gt(4_000_000_000_u32);
+    assert_eq!(p, Predicate::parse("(x < 5) && (x > 4000000000)").unwrap());
+}
@@ -0,0 +1,28 @@
+//! Helpers shared by the integration tests.
+
+// each test file only uses some of them
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use num::BigRational;
+use predicatechecker::Predicate;
+
+
+
+/// Return the predicate over `f64` written in the string, which must be valid.
+pub fn parse(txt: &str) -> Predicate<f64> {
+    Predicate::from(txt).unwrap()
+}
+
+
+/// Return the predicate over exact rational numbers written in the string, which must be valid.
+pub fn parse_rational(txt: &str) -> Predicate<BigRational> {
+    Predicate::parse(txt).unwrap()
+}
+
+
+/// Return the values of the arguments of a predicate over `f64`, given as pairs of a name and a value.
+pub fn env(values: &[(&str, f64)]) -> HashMap<String, f64> {
+    values.iter().map(|(a, x)| (a.to_string(), *x)).collect()
+}
@@ -0,0 +1,82 @@
+//! Implications, equivalences and exclusive disjunctions of predicates.
+
+mod common;
+
+use std::collections::HashMap;
+
+use predicatechecker::{Predicate, Implication, Placeholder, var, flag};
+use common::parse;
+
+
+
+#[test]
+fn syntax() {
+    let p = parse("premium => discount >= 10");
+    assert_eq!(p, Predicate::Implies(Box::new(flag("premium")), Box::new(var("discount").ge(10))));
+
+    // `=>` and `<=>` are applied after the other operators, and `=>` is right-associative
+    assert_eq!(parse("a && b => c <=> d xor e"), parse("((a && b) => c) <=> (d xor e)"));
+    assert_eq!(parse("a => b => c"), parse("a => (b => c)"));
+    assert_eq!(parse("a ⇒ b ⇔ c ⊕ d"), parse("(a => b) <=> (c xor d)"));
+    assert_eq!(flag("a") ^ flag("b"), parse("a xor b"));
+
+    for txt in ["a => (b => c)", "(a => b) => c", "(a <=> b) <=> c", "(a xor b) || c", "a && (b => c)", "a xor b xor c"] {
+        assert_eq!(parse(txt).to_string(), txt);
+    }
+}
+
+
+#[test]
+fn evaluation() {
+    let p = parse("(x > 0 => y > 0) && (a <=> x > 5) && (a xor b)");
+    let env = |x: f64, y: f64, a: bool, b: bool| {
+        let mut env = predicatechecker::Assignment::new();
+        env.set_value("x", x);
+        env.set_value("y", y);
+        env.set_bool("a", a);
+        env.set_bool("b", b);
+        env
+    };
+
+    assert_eq!(p.evaluate(&env(6.0, 1.0, true, false)), Ok(true));
+    assert_eq!(p.evaluate(&env(6.0, -1.0, true, false)), Ok(false));
+    assert_eq!(p.evaluate(&env(-1.0, -1.0, false, true)), Ok(true));
+    assert_eq!(p.evaluate(&env(-1.0, -1.0, true, false)), Ok(false));
+    assert_eq!(p.evaluate(&env(6.0, 1.0, true, true)), Ok(false));
+
+    // the second operand of an implication is not needed when the first one is false
+    assert_eq!(parse("x > 0 => y > 0").evaluate(&HashMap::from([("x".to_string(), -1.0)])), Ok(true));
+}
+
+
+#[test]
+fn reasoning() {
+    let rule = parse("premium => discount >= 10");
+    assert_eq!(parse("(premium && discount >= 12) || ! premium").implies(&rule), Implication::Total);
+    assert_eq!(parse("premium && rule_applies").implies(&rule), Implication::Partial { coverage: None });
+    assert_eq!(parse("premium && discount < 10").implies(&rule), Implication::Inexistant);
+    assert_eq!(parse("(premium => discount >= 10) && premium").implies(&parse("discount > 5")), Implication::Total);
+    assert_eq!(parse("(premium <=> x > 0) && ! premium").get_domain("x"), parse("x <= 0").get_domain("x"));
+    assert_eq!(parse("(x > 0) xor (x > 5)").get_domain("x"), parse("x > 0 && x <= 5").get_domain("x"));
+    assert_eq!(rule.get_arguments(), parse("premium && discount > 0").get_arguments());
+
+    assert!(parse("(a => b) <=> (! a || b)").is_valid());
+    assert!(parse("(a xor b) <=> ! (a <=> b)").is_valid());
+    assert!(parse("(a xor a)").is_contradiction());
+}
+
+
+#[test]
+fn transformations() {
+    assert_eq!(parse("!(a => x > 5)").to_nnf(), parse("a && x <= 5"));
+    assert_eq!(parse("a <=> b").to_dnf(), parse("(a && b) || (! a && ! b)"));
+    assert_eq!(parse("true => (x > 1) && (x > 2)").simplify(), parse("x > 2"));
+    assert_eq!(parse("a xor false").simplify(), parse("a"));
+    assert_eq!(parse("(x > 1 && x > 2) <=> b").simplify(), parse("x > 2 <=> b"));
+
+    let p = parse("premium => discount >= 10");
+    assert_eq!(p.to_sql(Placeholder::QuestionMark).0, "NOT \"premium\" OR \"discount\" >= ?");
+    assert_eq!(p.to_smtlib().lines().nth(3), Some("(assert (=> premium (>= discount 10.0)))"));
+    assert_eq!(Predicate::from_smtlib(&p.to_smtlib()), Ok(p));
+    assert_eq!(Predicate::<f64>::from_smtlib("(declare-const a Bool) (assert (xor a (= a b)))"), Ok(parse("a xor (a <=> b)")));
+}
@@ -0,0 +1,140 @@
+//! Proportion of the values verifying a predicate that verify another one.
+
+mod common;
+
+use predicatechecker::{Predicate, Implication, Schema, Type};
+use common::parse;
+
+
+
+fn coverage(a: &str, b: &str) -> Option<f64> {
+    parse(a).coverage(&parse(b))
+}
+
+
+
+#[test]
+fn bounded_domains() {
+    assert_eq!(coverage("(x > 0) && (x < 10)", "(x < 2) || (x > 9)"), Some(0.3));
+    assert_eq!(coverage("(x > 0) && (x < 10)", "x < 20"), Some(1.0));
+    assert_eq!(coverage("(x > 0) && (x < 10)", "x >= 10"), Some(0.0));
+
+    // integers are counted
+    let a = Predicate::<i64>::parse("(x >= 1) && (x <= 10)").unwrap();
+    assert_eq!(a.coverage(&Predicate::parse("x > 7").unwrap()), Some(0.3));
+}
+
+
+#[test]
+fn several_arguments() {
+    let square = "(x > 0) && (x < 10) && (y > 0) && (y < 10)";
+    assert_eq!(coverage(square, "(x > 5) || (y > 5)"), Some(0.75));
+    assert_eq!(coverage(square, "(x > 5) && (y < 1)"), Some(0.05));
+    assert_eq!(coverage(square, "(x < 5) xor (y < 5)"), Some(0.5));
+
+    // the arguments are not measured independently from each other
+    assert_eq!(coverage("((x < 5) && (y < 5)) || ((x > 5) && (y > 5))", "x < 5"), None);
+    assert_eq!(coverage(&format!("{square} && (((x < 5) && (y < 5)) || ((x > 5) && (y > 5)))"), "x < 5"), Some(0.5));
+    assert_eq!(coverage(&format!("{square} && (((x < 5) && (y < 5)) || ((x > 5) && (y > 5)))"), "y > 5"), Some(0.5));
+}
+
+
+#[test]
+fn comparisons_that_are_not_measured() {
+    let square = "(x > 0) && (x < 1) && (y > 0) && (y < 1)";
+    assert_eq!(coverage(square, "x < y"), None);
+    assert_eq!(coverage(square, "x + y < 0.1"), None);
+
+    // nor the comparisons that overflow `T`
+    let a = Predicate::<i64>::parse("(x >= 0) && (x <= 10)").unwrap();
+    assert_eq!(a.coverage(&Predicate::parse("(x > 5) || (x < -9223372036854775808)").unwrap()), None);
+    assert_eq!(a.coverage(&Predicate::parse("(x > 5) || (x < -9223372036854775807)").unwrap()), Some(5.0 / 11.0));
+}
+
+
+#[test]
+fn unbounded_domains() {
+    assert_eq!(coverage("x > 0", "x > 5"), None);
+    assert_eq!(coverage("(x > 0) && (y > 0)", "(x > 5) || (y > 5)"), None);
+
+    // the arguments only used by B can have any value
+    assert_eq!(coverage("(x > 0) && (x < 1)", "y > 0"), None);
+
+    // but the part of A which is negligible can be unbounded
+    assert_eq!(coverage("((x > 0) && (x < 1)) || (x in {5, 6})", "x < 0.5"), Some(0.5));
+}
+
+
+#[test]
+fn points() {
+    assert_eq!(coverage("x in {1, 2, 3, 4}", "x > 1.5"), Some(0.75));
+    assert_eq!(coverage("x == 1", "x < 2"), Some(1.0));
+
+    // values of a lower dimension are negligible
+    assert_eq!(coverage("(x > 0) && (x < 1)", "x == 0.5"), Some(0.0));
+    assert_eq!(coverage("((x >= 0) && (x <= 10)) || (x == 20)", "x >= 10"), Some(0.0));
+}
+
+
+#[test]
+fn booleans() {
+    assert_eq!(coverage("enabled || premium", "enabled"), Some(2.0 / 3.0));
+    assert_eq!(coverage("(x > 0) && (x < 10)", "(x < 5) && enabled"), Some(0.25));
+    assert_eq!(coverage("enabled && (x > 0) && (x < 4)", "!enabled || (x > 1)"), Some(0.75));
+}
+
+
+#[test]
+fn contradictions() {
+    assert_eq!(coverage("(x > 1) && (x < 0)", "x > 0"), None);
+    assert_eq!(coverage("enabled && !enabled", "enabled"), None);
+}
+
+
+#[test]
+fn partial_implications() {
+    let a = parse("((x >= 0) && (x <= 1)) || ((x >= 2) && (x <= 5))");
+    assert_eq!(a.implies(&parse("x < 1.5")), Implication::Partial { coverage: Some(0.25) });
+    assert_eq!(a.implies(&parse("y < 1")), Implication::Partial { coverage: None });
+    assert_eq!(a.implies(&parse("x > 6")), Implication::Inexistant);
+    assert_eq!(parse("(x < 0) || (x > 1)").implies(&parse("x < 0")), Implication::Partial { coverage: None });
+
+    let a = parse("(x >= 0) && (x <= 10)");
+    assert_eq!(a.implies(&parse("x < 5")), Implication::Partial { coverage: Some(0.5) });
+    assert_eq!(a.implies(&parse("x < 2.5")), Implication::Partial { coverage: Some(0.25) });
+}
+
+
+#[test]
+fn unsatisfiable_operands() {
+    // the operands of a disjunction that are false do not make the implication partial
+    let a = parse("((x > 0) && (x < 0)) || (x > 5)");
+    assert_eq!(a.implies(&parse("x < 3")), Implication::Inexistant);
+    assert_eq!(a.implies(&parse("x > 4")), Implication::Total);
+
+    let (implication, witness) = a.implies_with_witness(&parse("x < 3"));
+    assert_eq!(implication, Implication::Inexistant);
+    assert!(*witness.unwrap().get_value("x").unwrap() > 5.0);
+}
+
+
+#[test]
+fn negligible_values() {
+    // the coverage of a partial implication is neither 0 nor 1, even if the measure of some values is negligible
+    let a = parse("((x >= 0) && (x <= 1)) || (x == 5)");
+    assert_eq!(a.coverage(&parse("x <= 1")), Some(1.0));
+    assert_eq!(a.implies(&parse("x <= 1")), Implication::Partial { coverage: None });
+    assert_eq!(a.implies(&parse("x == 5")), Implication::Partial { coverage: None });
+}
+
+
+#[test]
+fn schemas() {
+    let mut schema = Schema::new();
+    schema.declare("age", Type::IntRange(Some(0.0), Some(99.0)));
+
+    let a = schema.parse("age >= 18").unwrap();
+    let b = schema.parse("age < 28").unwrap();
+    assert_eq!(schema.coverage(&a, &b), Some(10.0 / 82.0));
+    assert_eq!(a.coverage(&b), None);
+}
@@ -0,0 +1,61 @@
+//! Reasoning about disjunctions of comparisons on several arguments.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use predicatechecker::{Predicate, Implication};
+use common::parse;
+
+
+
+#[test]
+fn arguments_are_not_projected() {
+    // each argument can be anything when the other one is positive
+    let a = parse("(x > 0) || (y > 0)");
+    assert_ne!(a.implies(&parse("x > 0")), Implication::Total);
+    assert_eq!(a.implies(&parse("(x > 0) || (y > -1)")), Implication::Total);
+    assert_eq!(parse("(x > 0) && (y > 0)").implies(&a), Implication::Total);
+
+    // x + y > 0 is implied by each case, but not by the union of their projections
+    let a = parse("((x > 1) && (y > -1)) || ((y > 1) && (x > -1))");
+    assert_eq!(a.implies(&parse("x + y > 0")), Implication::Total);
+    assert_ne!(parse("(x > -1) && (y > -1)").implies(&parse("x + y > 0")), Implication::Total);
+}
+
+
+#[test]
+fn domains_of_correlated_arguments() {
+    let p = parse("((x < 0) && (y == 1)) || ((x > 10) && (y == 2))");
+    assert_eq!(p.get_domain("x"), parse("(x < 0) || (x > 10)").get_domain("x"));
+    assert_eq!(parse("y == 2 && (x < 0 || x > 10) && x < 5").get_domain("x"), parse("x < 0").get_domain("x"));
+    assert!(parse("((x < 0) || (y < 0)) && (x + y > 0) && (x < -5) && (y < 5)").is_contradiction());
+}
+
+
+#[test]
+fn witnesses_are_in_a_single_case() {
+    let a = parse("((x > 0) && (y > 5)) || ((x < -5) && (y < 0))");
+    let (implication, witness) = a.implies_with_witness(&parse("x * 2 > y"));
+    assert_ne!(implication, Implication::Total);
+
+    let w = witness.unwrap();
+    assert_eq!(a.evaluate(&w), Ok(true));
+    assert_eq!(parse("x * 2 > y").evaluate(&w), Ok(false));
+}
+
+
+#[test]
+fn independent_disjunctions_are_searched_once() {
+    // proving x0 != 5 does not depend on the disjunctions on the other arguments
+    let clauses: Vec<String> = (0..40).map(|i| format!("(x{i} < 0 || x{i} > 10)")).collect();
+    let a = parse(&clauses.join(" && "));
+    let b = parse("x0 != 5");
+
+    let start = Instant::now();
+    assert_eq!(a.implies(&b), Implication::Total);
+    assert!(!Predicate::And(Box::new(a.clone()), Box::new(parse("(x39 == 5) || (x0 >= 11)"))).is_contradiction());
+    assert_eq!(a.get_domain("x20"), parse("x20 < 0 || x20 > 10").get_domain("x20"));
+    assert!(Predicate::And(Box::new(a), Box::new(parse("x7 == 3"))).is_contradiction());
+    assert!(start.elapsed() < Duration::from_secs(2), "took {:?}", start.elapsed());
+}
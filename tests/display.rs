@@ -0,0 +1,67 @@
+//! The text of a predicate is read back by the parser as the same predicate.
+
+use predicatechecker::{Predicate, Value};
+use proptest::prelude::*;
+
+
+
+fn arg() -> impl Strategy<Value = String> {
+    prop::sample::select(vec!["x", "y", "z", "rate", "a1"]).prop_map(String::from)
+}
+
+
+fn literal() -> impl Strategy<Value = f64> {
+    any::<f64>().prop_filter("literals are finite", |x| x.is_finite())
+}
+
+
+/// Linear values, as the parser rejects the products of two arguments.
+fn value() -> impl Strategy<Value = Value<f64>> {
+    let leaf = prop_oneof![
+        arg().prop_map(Value::Arg),
+        literal().prop_map(Value::Literal),
+    ];
+
+    leaf.prop_recursive(4, 16, 2, |inner| prop_oneof![
+        (inner.clone(), inner.clone()).prop_map(|(v1, v2)| Value::Add(Box::new(v1), Box::new(v2))),
+        (inner.clone(), inner.clone()).prop_map(|(v1, v2)| Value::Sub(Box::new(v1), Box::new(v2))),
+        inner.clone().prop_map(|v| Value::Neg(Box::new(v))),
+        (inner.clone(), literal()).prop_map(|(v, x)| Value::Mul(Box::new(v), Box::new(Value::Literal(x)))),
+        (literal(), inner).prop_map(|(x, v)| Value::Mul(Box::new(Value::Literal(x)), Box::new(v))),
+    ])
+}
+
+
+fn predicate() -> impl Strategy<Value = Predicate<f64>> {
+    let leaf = prop_oneof![
+        Just(Predicate::True),
+        Just(Predicate::False),
+        arg().prop_map(Predicate::BoolArg),
+        (value(), value()).prop_map(|(v1, v2)| Predicate::LowerThan(v1, v2)),
+        (value(), value()).prop_map(|(v1, v2)| Predicate::LowerEqual(v1, v2)),
+        (value(), value()).prop_map(|(v1, v2)| Predicate::GreaterThan(v1, v2)),
+        (value(), value()).prop_map(|(v1, v2)| Predicate::GreaterEqual(v1, v2)),
+        (value(), value()).prop_map(|(v1, v2)| Predicate::Equal(v1, v2)),
+        (value(), prop::collection::vec(literal(), 0..4)).prop_map(|(v, set)| Predicate::In(v, set)),
+        (value(), prop::collection::vec(literal(), 0..4)).prop_map(|(v, set)| Predicate::NotIn(v, set)),
+    ];
+
+    leaf.prop_recursive(4, 16, 2, |inner| prop_oneof![
+        inner.clone().prop_map(|p| Predicate::Not(Box::new(p))),
+        (inner.clone(), inner.clone()).prop_map(|(p1, p2)| Predicate::And(Box::new(p1), Box::new(p2))),
+        (inner.clone(), inner.clone()).prop_map(|(p1, p2)| Predicate::Or(Box::new(p1), Box::new(p2))),
+        (inner.clone(), inner.clone()).prop_map(|(p1, p2)| Predicate::Implies(Box::new(p1), Box::new(p2))),
+        (inner.clone(), inner.clone()).prop_map(|(p1, p2)| Predicate::Iff(Box::new(p1), Box::new(p2))),
+        (inner.clone(), inner).prop_map(|(p1, p2)| Predicate::Xor(Box::new(p1), Box::new(p2))),
+    ])
+}
+
+
+
+proptest! {
+    #[test]
+    fn display_round_trip(p in predicate()) {
+        let txt = p.to_string();
+        prop_assert_eq!(Predicate::from(&txt), Ok(p), "text: {}", txt);
+    }
+}
@@ -0,0 +1,40 @@
+//! Errors reported when parsing invalid predicates.
+
+use predicatechecker::{Predicate, ParseError, Span};
+
+
+
+fn err(txt: &str) -> ParseError {
+    Predicate::from(txt).unwrap_err()
+}
+
+
+
+#[test]
+fn kinds_and_spans() {
+    assert_eq!(err("x > 5 y"), ParseError::UnexpectedToken("y".to_string(), Span { start: 6, end: 7, line: 1, column: 7 }));
+    assert_eq!(err("(x > 5))"), ParseError::UnbalancedParenthesis(Span { start: 7, end: 8, line: 1, column: 8 }));
+    assert_eq!(err("((x > 5)"), ParseError::UnbalancedParenthesis(Span { start: 0, end: 1, line: 1, column: 1 }));
+    assert_eq!(err("x > "), ParseError::MissingOperand(">".to_string(), Span { start: 2, end: 3, line: 1, column: 3 }));
+    assert!(matches!(err("(x > 5) && "), ParseError::MissingOperand(op, s) if op == "&&" && s.start == 8));
+    assert!(matches!(err("x =! 5"), ParseError::UnknownOperator(op, s) if op == "=!" && s.start == 2 && s.end == 4));
+    assert!(matches!(err("x @ 5"), ParseError::UnexpectedToken(t, _) if t == "@"));
+}
+
+
+#[test]
+fn lines_and_columns() {
+    let e = err("(x > 5)\n&& (é < 2) $");
+    assert_eq!(e.span(), &Span { start: 20, end: 21, line: 2, column: 12 });
+    assert_eq!(e.to_string(), "Unexpected token '$' (line 2, column 12)");
+}
+
+
+#[test]
+fn rendering() {
+    let txt = "(x > 5) &&\n(y =! 2)";
+    assert_eq!(err(txt).render(txt), "Unknown operator '=!' (line 2, column 4)\n(y =! 2)\n   ^^");
+
+    let txt = "x > 5 &&";
+    assert_eq!(err(txt).render(txt), "Missing operand for '&&' (line 1, column 7)\nx > 5 &&\n      ^^");
+}
@@ -0,0 +1,72 @@
+//! Truth value of predicates for given values of their arguments.
+
+mod common;
+
+use std::collections::HashMap;
+
+use predicatechecker::{Predicate, Assignment, EvalError};
+use common::{parse, env};
+
+
+
+#[test]
+fn comparisons_and_connectives() {
+    let p = parse("(x + 2 * y > 10) && (x < 3)");
+    assert_eq!(p.evaluate(&env(&[("x", 2.0), ("y", 5.0)])), Ok(true));
+    assert_eq!(p.evaluate(&env(&[("x", 3.0), ("y", 10.0)])), Ok(false));
+    assert_eq!(p.evaluate(&env(&[("x", 0.0), ("y", 5.0)])), Ok(false));
+    assert_eq!(parse("x in {1, 2}").evaluate(&env(&[("x", 2.0)])), Ok(true));
+}
+
+
+#[test]
+fn boolean_arguments() {
+    let p = parse("enabled && (x > 0)");
+
+    let mut a = Assignment::new();
+    a.set_bool("enabled", true);
+    a.set_value("x", 1.0);
+    assert_eq!(p.evaluate(&a), Ok(true));
+
+    a.set_bool("enabled", false);
+    assert_eq!(p.evaluate(&a), Ok(false));
+}
+
+
+#[test]
+fn errors() {
+    assert_eq!(parse("x > y").evaluate(&env(&[("x", 1.0)])), Err(EvalError::Unbound("y".to_string())));
+    assert_eq!(parse("enabled").evaluate(&env(&[("enabled", 1.0)])), Err(EvalError::WrongType("enabled".to_string())));
+
+    let mut a = Assignment::new();
+    a.set_bool("x", true);
+    assert_eq!(parse("x > 0").evaluate(&a), Err(EvalError::WrongType("x".to_string())));
+}
+
+
+#[test]
+fn short_circuits() {
+    // the second operand is not evaluated when the first one gives the result
+    let empty = env(&[]);
+    assert_eq!(parse("(1 > 2) && (y > 0)").evaluate(&empty), Ok(false));
+    assert_eq!(parse("(1 < 2) || (y > 0)").evaluate(&empty), Ok(true));
+    assert_eq!(parse("(1 > 2) => (y > 0)").evaluate(&empty), Ok(true));
+
+    // but it is when it is needed, and the first operand always is
+    assert_eq!(parse("(1 < 2) && (y > 0)").evaluate(&empty), Err(EvalError::Unbound("y".to_string())));
+    assert_eq!(parse("(y > 0) || (1 < 2)").evaluate(&empty), Err(EvalError::Unbound("y".to_string())));
+    assert_eq!(parse("(1 < 2) <=> (y > 0)").evaluate(&empty), Err(EvalError::Unbound("y".to_string())));
+}
+
+
+#[test]
+fn overflows() {
+    let values = |x: i64| HashMap::from([("x".to_string(), x)]);
+    assert_eq!(Predicate::<i64>::parse("x + 1 > 0").unwrap().evaluate(&values(i64::MAX)), Err(EvalError::Overflow("x + 1".to_string())));
+    assert_eq!(Predicate::<i64>::parse("2 * x < 0").unwrap().evaluate(&values(i64::MIN)), Err(EvalError::Overflow("2 * x".to_string())));
+    assert_eq!(Predicate::<i64>::parse("-x > 0").unwrap().evaluate(&values(i64::MIN)), Err(EvalError::Overflow("- x".to_string())));
+    assert_eq!(Predicate::<i64>::parse("x - 1 < 0").unwrap().evaluate(&values(i64::MIN + 1)), Ok(true));
+
+    // floating-point values become infinite instead
+    assert_eq!(parse("x + x > 0").evaluate(&env(&[("x", f64::MAX)])), Ok(true));
+}
@@ -0,0 +1,61 @@
+//! Values of `f64` that are not finite numbers.
+
+mod common;
+
+use predicatechecker::{Predicate, Implication, ParseError, EvalError};
+use common::{parse, env};
+
+
+
+#[test]
+fn nan_literals_are_rejected() {
+    assert!(matches!(Predicate::from("x < NaN"), Err(ParseError::NotANumber(s)) if s.start == 4));
+    assert!(matches!(Predicate::from("(x > 0) || (nan == y)"), Err(ParseError::NotANumber(_))));
+    assert!(matches!(Predicate::<f64>::parse("x < 5 * NaN"), Err(ParseError::NotANumber(_))));
+}
+
+
+#[test]
+fn infinite_literals_are_unbounded() {
+    assert!(parse("x < inf").is_valid());
+    assert!(parse("x > -inf").is_valid());
+    assert!(parse("(x > inf) || (x == inf)").is_contradiction());
+    assert_eq!(parse("(x > 5) && (x < inf)").get_domain("x"), parse("x > 5").get_domain("x"));
+    assert_eq!(parse("x < -inf").get_domain("x"), Predicate::False.get_domain("x"));
+
+    assert_eq!(parse("x > 5").implies(&parse("x < inf")), Implication::Total);
+    assert_eq!(parse("x < inf").implies(&parse("x > 5")), Implication::Partial { coverage: None });
+}
+
+
+#[test]
+fn infinite_coefficients_are_not_reasoned_about() {
+    // like other non-linear comparisons, they are considered to have any truth value
+    assert!(parse("inf * x > 0").is_satisfiable());
+    assert!(!parse("inf * x > 0").is_valid());
+    assert!(parse("x < inf - inf").is_satisfiable());
+}
+
+
+#[test]
+fn nan_values_can_not_be_compared() {
+    let p = parse("(x > 5) || (x <= 5)");
+    assert_eq!(p.evaluate(&env(&[("x", f64::NAN)])), Err(EvalError::NotANumber("x".to_string())));
+    assert_eq!(p.evaluate(&env(&[("x", 1.0)])), Ok(true));
+
+    let p = parse("x - y > 0");
+    let values = env(&[("x", f64::INFINITY), ("y", f64::INFINITY)]);
+    assert_eq!(p.evaluate(&values), Err(EvalError::NotANumber("x - y".to_string())));
+
+    // the second operand is not evaluated when the first one is enough
+    let p = parse("(x > 0) || (y > 0)");
+    assert_eq!(p.evaluate(&env(&[("x", 1.0), ("y", f64::NAN)])), Ok(true));
+}
+
+
+#[test]
+fn infinite_values_are_compared() {
+    let p = parse("x > 1000000");
+    assert_eq!(p.evaluate(&env(&[("x", f64::INFINITY)])), Ok(true));
+    assert_eq!(p.evaluate(&env(&[("x", f64::NEG_INFINITY)])), Ok(false));
+}
@@ -0,0 +1,85 @@
+//! Operators of the infix notation other than the basic comparisons and boolean operators.
+
+mod common;
+
+use predicatechecker::{Predicate, Value, Implication, ParseError, Dialect};
+use common::parse;
+
+
+
+#[test]
+fn negations() {
+    assert_eq!(parse("x != 5"), parse("! (x == 5)"));
+    assert_eq!(parse("not enabled && x > 0"), parse("(! enabled) && x > 0"));
+    assert_eq!(parse("not (x > 0)").implies(&parse("x <= 0")), Implication::Total);
+
+    assert!(matches!(Predicate::<f64>::parse("x not y"), Err(ParseError::UnexpectedToken(t, _)) if t == "not"));
+
+    // the negation is applied after the comparisons, but before the boolean operators
+    assert_eq!(parse("not x > 5"), parse("!(x > 5)"));
+    assert_eq!(parse("!x + 1 > 5 && a"), parse("!(x + 1 > 5) && a"));
+    assert_eq!(parse("¬ 0 <= x < 10"), parse("!(0 <= x && x < 10)"));
+    assert_eq!(parse("!a || b"), parse("(!a) || b"));
+    assert_eq!(parse("- x > 5"), parse("(-x) > 5"));
+}
+
+
+#[test]
+fn chained_comparisons() {
+    assert_eq!(parse("0 <= x < 10"), parse("0 <= x && x < 10"));
+    assert_eq!(parse("0 < x + 1 <= y < 5"), parse("0 < x + 1 && x + 1 <= y && y < 5"));
+    assert_eq!(parse("a || 0 < x < 1"), parse("a || (0 < x && x < 1)"));
+    assert_eq!(parse("0 <= x < 10").implies(&parse("x > -1")), Implication::Total);
+
+    // parentheses end the chains
+    assert_eq!(parse("(0 <= x < 10) && y > 0"), parse("0 <= x && x < 10 && y > 0"));
+    assert!(matches!(Predicate::<f64>::parse("(0 <= x) < 10"), Err(ParseError::MissingOperand(op, _)) if op == "<"));
+    assert!(matches!(Predicate::<f64>::parse("0 <= (x < 10)"), Err(ParseError::MissingOperand(op, _)) if op == "<="));
+    assert_eq!(parse("(x + 1) < 10"), parse("x + 1 < 10"));
+}
+
+
+#[test]
+fn intervals() {
+    assert_eq!(parse("x in [0, 10)"), parse("x >= 0 && x < 10"));
+    assert_eq!(parse("2 * x in (-1, 5]"), parse("2 * x > -1 && 2 * x <= 5"));
+    assert_eq!(parse("x in [0, 10) && y in (0,1)").implies(&parse("x < 10")), Implication::Total);
+
+    assert!(matches!(Predicate::<f64>::parse("x in [0, 10"), Err(ParseError::UnbalancedParenthesis(s)) if s.start == 5));
+    assert!(matches!(Predicate::<f64>::parse("x in [0; 10]"), Err(ParseError::UnexpectedToken(_, _))));
+    assert!(matches!(Predicate::<f64>::parse("x in [0, y]"), Err(ParseError::UnexpectedToken(t, _)) if t == "y"));
+    assert!(matches!(Predicate::<f64>::parse("x in 5"), Err(ParseError::MissingOperand(op, _)) if op == "in"));
+    assert!(matches!(Predicate::<f64>::parse("x < [0, 1]"), Err(ParseError::UnexpectedToken(t, _)) if t == "["));
+}
+
+
+#[test]
+fn spellings() {
+    assert_eq!(parse("(x>5)&&!(y<=-2)||z!=x*2"), parse("(x > 5) && ! (y <= -2) || z != x * 2"));
+    assert_eq!(parse("x > 5 and not (y ≤ 2 or a) ∨ ¬b ∧ z ≥ 1 ∧ z ≠ 3"), parse("x > 5 && !(y <= 2 || a) || ! b && z >= 1 && z != 3"));
+    assert_eq!(parse("x in{1,2}and y in[0,1)"), parse("x in {1, 2} && y in [0, 1)"));
+
+    // a `-` followed by a number is part of it only where an operand is expected
+    let neg = |v: Value<f64>| Value::Neg(Box::new(v));
+    assert_eq!(parse("x-5 > -5"), Predicate::GreaterThan(
+        Value::Sub(Box::new(Value::Arg("x".to_string())), Box::new(Value::Literal(5.0))),
+        Value::Literal(-5.0)
+    ));
+    assert_eq!(parse("- 5 < - -x"), Predicate::LowerThan(neg(Value::Literal(5.0)), neg(neg(Value::Arg("x".to_string())))));
+    assert_eq!(parse("x < -inf"), Predicate::LowerThan(Value::Arg("x".to_string()), Value::Literal(f64::NEG_INFINITY)));
+    assert_eq!(parse("x >= 1e-3"), parse("x >= 0.001"));
+
+    assert!(matches!(Predicate::<f64>::parse("x = 5"), Err(ParseError::UnknownOperator(op, s)) if op == "=" && s.start == 2));
+    assert!(matches!(Predicate::<f64>::parse("x =< 5"), Err(ParseError::UnknownOperator(op, _)) if op == "=<"));
+    assert!(matches!(Predicate::<f64>::parse("2x > 5"), Err(ParseError::UnexpectedToken(t, _)) if t == "2x"));
+    assert!(matches!(Predicate::<f64>::parse("x > 5 ; y"), Err(ParseError::UnexpectedToken(t, s)) if t == ";" && s.start == 6));
+}
+
+
+#[test]
+fn dialects() {
+    let single = |txt: &str| Predicate::<f64>::parse_with(txt, Dialect::SingleEquals).unwrap();
+    assert_eq!(single("x=5 and y == 2"), parse("x == 5 && y == 2"));
+    assert_eq!(single("x>=5"), parse("x >= 5"));
+    assert_eq!(Predicate::<f64>::parse_with("x == 5", Dialect::DoubleEquals), Ok(parse("x == 5")));
+}
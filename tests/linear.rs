@@ -0,0 +1,44 @@
+//! Comparisons of linear expressions of the arguments.
+
+mod common;
+
+use predicatechecker::{Predicate, Value, Implication, ParseError};
+use common::parse;
+
+
+
+#[test]
+fn precedence_of_arithmetic_operators() {
+    let x = || Box::new(Value::Arg("x".to_string()));
+    let lit = |v: f64| Box::new(Value::Literal(v));
+
+    assert_eq!(parse("x + 2 * 3 > 0"), Predicate::GreaterThan(Value::Add(x(), Box::new(Value::Mul(lit(2.0), lit(3.0)))), Value::Literal(0.0)));
+    assert_eq!(parse("x - 1 - 2 > 0"), Predicate::GreaterThan(Value::Sub(Box::new(Value::Sub(x(), lit(1.0))), lit(2.0)), Value::Literal(0.0)));
+    assert_eq!(parse("-x * 2 > 0"), Predicate::GreaterThan(Value::Mul(Box::new(Value::Neg(x())), lit(2.0)), Value::Literal(0.0)));
+    assert_eq!(parse("2 * (x + 1) > 0").implies(&parse("x > -1")), Implication::Total);
+}
+
+
+#[test]
+fn linear_implications() {
+    assert_eq!(parse("(2 * x + y <= 10) && (x >= 0) && (y >= 0)").implies(&parse("x <= 5")), Implication::Total);
+    assert_eq!(parse("x - 3 > y").implies(&parse("x > y")), Implication::Total);
+    assert_eq!(parse("(x + y > 10) && (x < 3)").implies(&parse("y > 7")), Implication::Total);
+    assert_ne!(parse("(x + y > 10) && (x < 3)").implies(&parse("y > 8")), Implication::Total);
+    assert!(parse("(x + y > 2) && (x < 1) && (y < 1)").is_contradiction());
+}
+
+
+#[test]
+fn domains_of_linear_constraints() {
+    assert_eq!(parse("3 * x - 6 < 0").get_domain("x"), parse("x < 2").get_domain("x"));
+    assert_eq!(parse("(x + y == 4) && (y >= 1) && (y <= 3)").get_domain("x"), parse("(x >= 1) && (x <= 3)").get_domain("x"));
+}
+
+
+#[test]
+fn products_of_arguments_are_rejected() {
+    assert!(matches!(Predicate::from("x * y > 2"), Err(ParseError::NonLinearProduct(s)) if s.start == 0 && s.end == 5));
+    assert!(matches!(Predicate::from("(x + 1) * (y - 1) > 2"), Err(ParseError::NonLinearProduct(_))));
+    assert!(Predicate::from("(1 + 2) * (y - 1) > 2").is_ok());
+}
@@ -0,0 +1,85 @@
+//! Normal forms and simplification of predicates.
+
+mod common;
+
+use predicatechecker::Predicate;
+use common::parse;
+
+
+
+const PREDICATES: [&str; 5] = [
+    "!((x > 5) || (y < 2)) && a",
+    "(a => (x == 1)) <=> !(b || (y != 2))",
+    "((a && b) || (x > 5)) && !(c xor (y <= x))",
+    "!!(x in {1, 2}) || !(a && !(b => c))",
+    "(x > 1) && ((y < 2) || ((z >= 3) && !a))",
+];
+
+
+/// Return true if the predicate is a comparison, a boolean argument, or the negation of one.
+fn is_literal(p: &Predicate<f64>) -> bool {
+    match p {
+        Predicate::Not(p) => !matches!(**p, Predicate::Not(_)) && is_literal(p),
+        Predicate::And(..) | Predicate::Or(..) | Predicate::Implies(..) | Predicate::Iff(..) | Predicate::Xor(..) => false,
+        _ => true
+    }
+}
+
+/// Return true if the predicate is only made of `And` (if `and`) or `Or` operations of predicates verifying `f`.
+fn is_flat(p: &Predicate<f64>, and: bool, f: &dyn Fn(&Predicate<f64>) -> bool) -> bool {
+    match p {
+        Predicate::And(p1, p2) if and => is_flat(p1, and, f) && is_flat(p2, and, f),
+        Predicate::Or(p1, p2) if !and => is_flat(p1, and, f) && is_flat(p2, and, f),
+        p => f(p)
+    }
+}
+
+fn is_nnf(p: &Predicate<f64>) -> bool {
+    match p {
+        Predicate::And(p1, p2) | Predicate::Or(p1, p2) => is_nnf(p1) && is_nnf(p2),
+        Predicate::Not(p) => matches!(**p, Predicate::BoolArg(_) | Predicate::Equal(..)),
+        p => is_literal(p)
+    }
+}
+
+
+
+#[test]
+fn negation_normal_form() {
+    assert_eq!(parse("!((x > 5) && !a)").to_nnf(), parse("(x <= 5) || a"));
+    assert_eq!(parse("!(x == 5)").to_nnf(), parse("!(x == 5)"));
+    for txt in PREDICATES {
+        let nnf = parse(txt).to_nnf();
+        assert!(is_nnf(&nnf), "{txt}: {nnf}");
+        assert!(nnf.equivalent(&parse(txt)), "{txt}: {nnf}");
+    }
+}
+
+
+#[test]
+fn conjunctive_and_disjunctive_normal_forms() {
+    for txt in PREDICATES {
+        let cnf = parse(txt).to_cnf();
+        assert!(is_flat(&cnf, true, &|c| is_flat(c, false, &is_literal)), "{txt}: {cnf}");
+        assert!(cnf.equivalent(&parse(txt)), "{txt}: {cnf}");
+
+        let dnf = parse(txt).to_dnf();
+        assert!(is_flat(&dnf, false, &|c| is_flat(c, true, &is_literal)), "{txt}: {dnf}");
+        assert!(dnf.equivalent(&parse(txt)), "{txt}: {dnf}");
+    }
+}
+
+
+#[test]
+fn simplification() {
+    assert_eq!(parse("(5 > 4) && (x < 2)").simplify(), parse("x < 2"));
+    assert_eq!(parse("!!a || (3 < 2)").simplify(), parse("a"));
+    assert_eq!(parse("a && !a && (x > 0)").simplify(), Predicate::False);
+    assert_eq!(parse("(x > 5) || b || !b").simplify(), Predicate::True);
+    assert_eq!(parse("(x > 1) && (x < 10) && (x > 3) && (y > 0)").simplify(), parse("(x > 3) && (x < 10) && (y > 0)"));
+    assert_eq!(parse("(x > 1) && (x < 0)").simplify(), Predicate::False);
+
+    for txt in PREDICATES {
+        assert!(parse(txt).simplify().equivalent(&parse(txt)), "{txt}");
+    }
+}
@@ -0,0 +1,49 @@
+//! Satisfiability, validity and equivalence of predicates.
+
+mod common;
+
+use predicatechecker::{Predicate, Implication};
+use common::parse;
+
+
+
+#[test]
+fn satisfiability() {
+    assert!(parse("(x > 5) || (y < 0)").is_satisfiable());
+    assert!(parse("(x < y) && (y < z)").is_satisfiable());
+    assert!(!parse("(x > 5) && (x < 5)").is_satisfiable());
+    assert!(!Predicate::<f64>::False.is_satisfiable());
+    assert!(parse("(x > 5) && (x < 5)").is_contradiction());
+}
+
+
+#[test]
+fn validity() {
+    assert!(parse("(x > 5) || (x <= 5)").is_valid());
+    assert!(parse("(x < y) || (x >= y)").is_valid());
+    assert!(parse("enabled || !enabled").is_valid());
+    assert!(!parse("(x > 5) || (y <= 5)").is_valid());
+    assert!(Predicate::<f64>::True.is_valid());
+}
+
+
+#[test]
+fn equivalence() {
+    assert!(Predicate::Not(Box::new(parse("(x > 5) && (y > 5)"))).equivalent(&parse("(x <= 5) || (y <= 5)")));
+    assert!(parse("x - y < 0").equivalent(&parse("y > x")));
+    assert!(parse("(x > 5) || (x > 6)").equivalent(&parse("x > 5")));
+    assert!(!parse("x > 5").equivalent(&parse("x >= 5")));
+}
+
+
+#[test]
+fn queries_are_consistent() {
+    // what `implies` gives when emulating the queries with it, disjunctions included
+    for txt in ["(x > 5) || (x <= 5)", "(x > 5) || (y < 0)", "(x > 5) && (x < 5)", "(x < y) || (y < x)"] {
+        let p = parse(txt);
+        assert_eq!(p.is_valid(), Predicate::True.implies(&p) == Implication::Total, "{txt}");
+        assert_eq!(p.is_contradiction(), p.implies(&Predicate::False) == Implication::Total, "{txt}");
+        assert_eq!(p.is_valid(), Predicate::Not(Box::new(p.clone())).is_contradiction(), "{txt}");
+        assert!(p.equivalent(&p.clone()));
+    }
+}
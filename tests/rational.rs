@@ -0,0 +1,66 @@
+//! Predicates over exact rational numbers.
+
+mod common;
+
+use num::{BigRational, BigInt};
+use predicatechecker::{Implication, Assignment};
+use common::parse_rational as parse;
+
+
+
+fn ratio(numer: i64, denom: i64) -> BigRational {
+    BigRational::new(BigInt::from(numer), BigInt::from(denom))
+}
+
+
+
+#[test]
+fn decimal_and_fraction_literals() {
+    assert_eq!(parse("x <= 0.25"), parse("x <= 1/4"));
+    assert_eq!(parse("x <= -12.75"), parse("x <= -51/4"));
+    assert_eq!(parse("x <= 3"), parse("x <= 6/2"));
+}
+
+
+#[test]
+fn boundaries_are_exact() {
+    let a = parse("x <= 0.1 + 0.2");
+    assert_eq!(a.implies(&parse("x <= 0.3")), Implication::Total);
+    assert_eq!(parse("x <= 0.3").implies(&a), Implication::Total);
+    assert_eq!(a.implies(&parse("x < 0.3")), Implication::Partial { coverage: None });
+
+    let a = parse("3 * x < 1");
+    assert_eq!(a.implies(&parse("x < 1/3")), Implication::Total);
+    assert_eq!(a.implies(&parse("x < 0.3333333333")), Implication::Partial { coverage: None });
+}
+
+
+#[test]
+fn domains() {
+    let p = parse("(x > 1/3) && (x + y <= 1) && (y >= 1/2)");
+    assert_eq!(p.get_domain("x"), parse("(x > 1/3) && (x <= 1/2)").get_domain("x"));
+    assert!(parse("(x > 1/3) && (x < 0.33334) && (3 * x > 1.00002)").is_contradiction());
+}
+
+
+#[test]
+fn witnesses() {
+    let a = parse("(x > 1/3) && (x < 1/2)");
+    let (implication, witness) = a.implies_with_witness(&parse("x < 0.4"));
+    assert!(matches!(implication, Implication::Partial { coverage: Some(c) } if (c - 0.4).abs() < 1e-9));
+
+    let x = witness.unwrap().get_value("x").unwrap().clone();
+    assert!(x >= ratio(2, 5) && x < ratio(1, 2));
+
+    let mut env = Assignment::new();
+    env.set_value("x", ratio(1, 3));
+    assert_eq!(a.evaluate(&env), Ok(false));
+}
+
+
+#[test]
+fn display_round_trip() {
+    let p = parse("(x > -1/3) && (2/7 * y - 0.5 <= x)");
+    assert_eq!(p.to_string(), "x > -1/3 && 2/7 * y - 1/2 <= x");
+    assert_eq!(parse(&p.to_string()), p);
+}
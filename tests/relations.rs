@@ -0,0 +1,43 @@
+//! Comparisons between two arguments.
+
+mod common;
+
+use predicatechecker::Implication;
+use common::parse;
+
+
+
+#[test]
+fn transitivity() {
+    assert_eq!(parse("(x < y) && (y < 5)").implies(&parse("x < 5")), Implication::Total);
+    assert_eq!(parse("(x <= y) && (y <= z)").implies(&parse("x <= z")), Implication::Total);
+    assert_eq!(parse("(x < y) && (y <= z) && (z <= w)").implies(&parse("x < w")), Implication::Total);
+    assert_ne!(parse("(x <= y) && (y <= z)").implies(&parse("x < z")), Implication::Total);
+    assert_ne!(parse("(x < y) && (z < y)").implies(&parse("x < z")), Implication::Total);
+}
+
+
+#[test]
+fn equalities() {
+    assert_eq!(parse("(x == y) && (y == 3)").implies(&parse("x == 3")), Implication::Total);
+    assert_eq!(parse("(x >= y) && (x <= y)").implies(&parse("x == y")), Implication::Total);
+    assert!(parse("(x == y) && (x > y)").is_contradiction());
+    assert!(parse("x == x").is_valid());
+    assert!(parse("x < x").is_contradiction());
+}
+
+
+#[test]
+fn cycles() {
+    assert!(parse("(x < y) && (y < z) && (z < x)").is_contradiction());
+    assert!(parse("(x <= y) && (y <= z) && (z <= x)").is_satisfiable());
+    assert_eq!(parse("(x <= y) && (y <= z) && (z <= x)").implies(&parse("x == z")), Implication::Total);
+}
+
+
+#[test]
+fn domains_through_other_arguments() {
+    assert_eq!(parse("(x < y) && (y < 5)").get_domain("x"), parse("x < 5").get_domain("x"));
+    assert_eq!(parse("(0 <= x) && (x <= y) && (y <= 10)").get_domain("y"), parse("(y >= 0) && (y <= 10)").get_domain("y"));
+    assert!(parse("(x < y) && (y < 0) && (x > 0)").get_domain("y").is_empty());
+}
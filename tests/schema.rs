@@ -0,0 +1,62 @@
+//! Predicates over arguments declared with their type.
+
+use predicatechecker::{Predicate, Schema, Type, Implication, ParseError};
+
+
+
+fn schema() -> Schema<f64> {
+    let mut schema = Schema::new();
+    schema.declare("age", Type::IntRange(Some(0.0), Some(150.0)));
+    schema.declare("count", Type::Int);
+    schema.declare("price", Type::RealRange(Some(0.0), None));
+    schema.declare("ratio", Type::Real);
+    schema.declare("premium", Type::Bool);
+    schema
+}
+
+
+
+#[test]
+fn undeclared_arguments_are_rejected() {
+    let schema = schema();
+    assert!(matches!(schema.parse("(age > 5) && (height < 2)"), Err(ParseError::UndeclaredArgument(a, s)) if a == "height" && s.start == 14));
+    assert!(matches!(schema.parse("premium || vip"), Err(ParseError::UndeclaredArgument(a, _)) if a == "vip"));
+    assert!(schema.parse("(age > 5) && premium && (ratio * 2 < price)").is_ok());
+}
+
+
+#[test]
+fn arguments_are_used_according_to_their_type() {
+    let schema = schema();
+    assert!(matches!(schema.parse("premium > 5"), Err(ParseError::WrongType(a, s)) if a == "premium" && s.start == 0));
+    assert!(matches!(schema.parse("age && premium"), Err(ParseError::WrongType(a, _)) if a == "age"));
+    assert!(matches!(schema.parse("(price + premium) < 5"), Err(ParseError::WrongType(a, _)) if a == "premium"));
+    assert!(matches!(schema.parse("!count"), Err(ParseError::WrongType(a, _)) if a == "count"));
+}
+
+
+#[test]
+fn ranges_restrict_the_values() {
+    let schema = schema();
+    let p = schema.parse("age < 1").unwrap();
+    assert_eq!(schema.implies(&p, &schema.parse("age == 0").unwrap()), Implication::Total);
+    assert_ne!(p.implies(&Predicate::from("age == 0").unwrap()), Implication::Total);
+
+    assert_eq!(schema.get_domain(&p, "age"), Predicate::from("age == 0").unwrap().get_domain("age"));
+    assert_eq!(schema.get_domain(&schema.parse("price < 10").unwrap(), "price"), Predicate::from("(price >= 0) && (price < 10)").unwrap().get_domain("price"));
+    assert_eq!(schema.get_range("price"), Some(Predicate::from("price >= 0").unwrap()));
+    assert_eq!(schema.get_range("count"), None);
+}
+
+
+#[test]
+fn integer_types_have_no_values_between_integers() {
+    let schema = schema();
+    assert_eq!(schema.implies(&schema.parse("count > 5").unwrap(), &schema.parse("count >= 6").unwrap()), Implication::Total);
+    assert_eq!(schema.implies(&schema.parse("(count > 5) && (count < 6)").unwrap(), &Predicate::False), Implication::Total);
+    assert_ne!(schema.implies(&schema.parse("ratio > 5").unwrap(), &schema.parse("ratio >= 6").unwrap()), Implication::Total);
+
+    let (implication, witness) = schema.implies_with_witness(&schema.parse("age >= 149.5").unwrap(), &schema.parse("age < 150").unwrap());
+    assert_ne!(implication, Implication::Total);
+    assert_eq!(witness.unwrap().get_value("age"), Some(&150.0));
+}
@@ -0,0 +1,96 @@
+//! JSON representation of predicates and domains, with the `serde` feature.
+
+#![cfg(feature = "serde")]
+
+use num::{BigRational, BigInt};
+use serde_json::json;
+use predicatechecker::{Predicate, Value, Implication, Domain, Interval};
+
+
+
+#[test]
+fn predicate_format() {
+    let p = Predicate::from("(x > 5) && !(2 * y == - z) || enabled").unwrap();
+    let node = |op: &str, left, right| json!({"op": op, "left": left, "right": right});
+    let arg = |name: &str| json!({"op": "arg", "name": name});
+    let literal = |value: f64| json!({"op": "literal", "value": value});
+
+    let expected = json!({"version": 2, "predicate": node("or",
+        node("and",
+            node("gt", arg("x"), literal(5.0)),
+            json!({"op": "not", "operand": node("eq", node("mul", literal(2.0), arg("y")), json!({"op": "neg", "operand": arg("z")}))})
+        ),
+        json!({"op": "bool_arg", "name": "enabled"})
+    )});
+
+    assert_eq!(serde_json::to_value(&p).unwrap(), expected);
+    assert_eq!(serde_json::from_value::<Predicate<f64>>(expected).unwrap(), p);
+}
+
+
+#[test]
+fn round_trip() {
+    let p = Predicate::<BigRational>::parse("(x <= 1/3) || (0.25 * x + y > -2) || (z not in {1/2, 3}) || (a => b <=> c xor d)").unwrap();
+    let txt = serde_json::to_string(&p).unwrap();
+    assert_eq!(serde_json::from_str::<Predicate<BigRational>>(&txt).unwrap(), p);
+
+    let v = Value::Sub(Box::new(Value::Arg("x".to_string())), Box::new(Value::Literal(3_i64)));
+    let txt = serde_json::to_string(&v).unwrap();
+    assert_eq!(txt, r#"{"version":2,"value":{"op":"sub","left":{"op":"arg","name":"x"},"right":{"op":"literal","value":3}}}"#);
+    assert_eq!(serde_json::from_str::<Value<i64>>(&txt).unwrap(), v);
+
+    for i in [Implication::Total, Implication::Partial { coverage: Some(0.25) }, Implication::Partial { coverage: None }, Implication::Inexistant] {
+        let txt = serde_json::to_string(&i).unwrap();
+        assert_eq!(serde_json::from_str::<Implication>(&txt).unwrap(), i);
+    }
+    assert_eq!(serde_json::to_value(Implication::Total).unwrap(), json!("total"));
+    assert_eq!(serde_json::to_value(Implication::Partial { coverage: Some(0.5) }).unwrap(), json!({"partial": {"coverage": 0.5}}));
+}
+
+
+#[test]
+fn domain_format() {
+    let d = Predicate::from("(x > 5) && (x <= 10) || (x < -1)").unwrap().get_domain("x");
+    let json = serde_json::to_value(&d).unwrap();
+    assert_eq!(json["version"], json!(2));
+    assert_eq!(json["integer"], json!(false));
+    assert_eq!(serde_json::from_value::<Domain<f64>>(json).unwrap(), d);
+
+    let i = Interval::new(Some(5.0), false, None, false);
+    assert_eq!(serde_json::to_value(&i).unwrap(), json!({"lower": {"value": 5.0, "inclusive": false}, "upper": null}));
+
+    // integer domains only have included integer bounds
+    let d: Domain<f64> = serde_json::from_value(json!({"version": 1, "integer": true, "intervals": [
+        {"lower": {"value": 5.0, "inclusive": false}, "upper": {"value": 7.5, "inclusive": true}}
+    ]})).unwrap();
+    assert_eq!(d, Domain::new(Some(6.0), true, Some(7.0), true));
+
+    let d: Domain<BigRational> = serde_json::from_value(json!({"version": 1, "integer": false, "intervals": [
+        {"lower": null, "upper": {"value": [[1, [1]], [1, [3]]], "inclusive": true}}
+    ]})).unwrap();
+    assert_eq!(d, Domain::new(None, false, Some(BigRational::new(BigInt::from(1), BigInt::from(3))), true));
+}
+
+
+#[test]
+fn unsupported_version() {
+    let json = json!({"version": 3, "predicate": {"op": "true"}});
+    let err = serde_json::from_value::<Predicate<f64>>(json).unwrap_err();
+    assert!(err.to_string().contains("unsupported format version 3"));
+    assert!(serde_json::from_value::<Predicate<f64>>(json!({"version": 0, "predicate": {"op": "true"}})).is_err());
+
+    assert!(serde_json::from_value::<Predicate<f64>>(json!({"version": 1, "predicate": {"op": "xor"}})).is_err());
+}
+
+
+#[test]
+fn previous_version() {
+    // the version 1 only differs by the format of partial implications, which had no coverage
+    let p = json!({"version": 1, "predicate": {"op": "not", "operand": {"op": "bool_arg", "name": "enabled"}}});
+    assert_eq!(serde_json::from_value::<Predicate<f64>>(p).unwrap(), Predicate::from("!enabled").unwrap());
+    assert_eq!(serde_json::from_value::<Value<i64>>(json!({"version": 1, "value": {"op": "arg", "name": "x"}})).unwrap(), Value::Arg("x".to_string()));
+
+    assert_eq!(serde_json::from_value::<Implication>(json!("partial")).unwrap(), Implication::Partial { coverage: None });
+    assert_eq!(serde_json::from_value::<Implication>(json!("inexistant")).unwrap(), Implication::Inexistant);
+    assert!(serde_json::from_value::<Implication>(json!("unknown")).is_err());
+}
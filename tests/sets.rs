@@ -0,0 +1,66 @@
+//! Predicates testing whether a value is in a finite set.
+
+mod common;
+
+use predicatechecker::{Predicate, Value, Implication, Domain, Placeholder, ParseError};
+use common::parse;
+
+
+
+#[test]
+fn syntax() {
+    let p = parse("x in {1, 2, -5} && y not in {}");
+    assert_eq!(p, Predicate::And(
+        Box::new(Predicate::In(Value::Arg("x".to_string()), vec![1.0, 2.0, -5.0])),
+        Box::new(Predicate::NotIn(Value::Arg("y".to_string()), vec![]))
+    ));
+    assert_eq!(p.to_string(), "x in {1, 2, -5} && y not in {}");
+    assert_eq!(parse("x not in [0, 1)"), parse("!(x in [0, 1))"));
+
+    assert!(matches!(Predicate::<f64>::parse("x in {1, 2"), Err(ParseError::UnbalancedParenthesis(s)) if s.start == 5));
+    assert!(matches!(Predicate::<f64>::parse("x in {1, y}"), Err(ParseError::UnexpectedToken(t, _)) if t == "y"));
+    assert!(matches!(Predicate::<f64>::parse("x in {1,}"), Err(ParseError::UnexpectedToken(t, _)) if t == "}"));
+    assert!(matches!(Predicate::<f64>::parse("x == {1}"), Err(ParseError::UnexpectedToken(t, _)) if t == "{"));
+}
+
+
+#[test]
+fn implications() {
+    let a = parse("x in {1, 2}");
+    assert_eq!(a.implies(&parse("x in {1, 2, 3}")), Implication::Total);
+    assert_eq!(a.implies(&parse("x < 10")), Implication::Total);
+    assert_eq!(a.implies(&parse("x not in {3, 4}")), Implication::Total);
+    assert_eq!(parse("x in {1, 2, 3}").implies(&a), Implication::Partial { coverage: Some(2.0 / 3.0) });
+    assert_eq!(parse("x in {1, 2, 3}").implies(&parse("x > 5")), Implication::Inexistant);
+    assert_eq!(parse("x in {}").implies(&Predicate::False), Implication::Total);
+
+    // the values between the elements of the set are not in it
+    let b = Predicate::<i64>::parse("x not in {1, 2} && x > 0 && x < 5").unwrap();
+    assert_eq!(b.implies(&Predicate::parse("x in {3, 4}").unwrap()), Implication::Total);
+    assert_eq!(parse("x not in {1, 2} && x > 0 && x < 5").implies(&parse("x in {3, 4}")), Implication::Partial { coverage: None });
+}
+
+
+#[test]
+fn domains() {
+    let p = parse("x in {5, 1, 2, 1} && y in {0}");
+    assert_eq!(p.get_domain("x"), Domain::points(vec![1.0, 2.0, 5.0]));
+    assert_eq!(p.get_domain("y"), Domain::point(0.0));
+    assert_eq!(p.get_domain("x").to_predicate("x"), parse("x in {1, 2, 5}"));
+
+    assert_eq!(parse("(x == 1) || (x == 3) || (x == 1)").simplify(), parse("x in {1, 3}"));
+    assert_eq!(parse("x in {1, 2, 3} && x > 1").simplify(), parse("x in {2, 3}"));
+    assert_eq!(parse("!(x in {1, 2})").simplify(), parse("x not in {1, 2}"));
+}
+
+
+#[test]
+fn conversions() {
+    let p = parse("x in {1, 2} || y not in {3}");
+    let (sql, params) = p.to_sql(Placeholder::QuestionMark);
+    assert_eq!(sql, "\"x\" IN (?, ?) OR \"y\" NOT IN (?)");
+    assert_eq!(Predicate::from_sql(&sql, &params), Ok(p.clone()));
+
+    assert!(p.to_smtlib().contains("(assert (or (or (= x 1.0) (= x 2.0)) (not (= y 3.0))))"));
+    assert!(Predicate::from_smtlib(&p.to_smtlib()).unwrap().equivalent(&p));
+}
@@ -0,0 +1,117 @@
+//! Conversion of predicates from and to SMT-LIB 2 scripts.
+
+use num::BigRational;
+use predicatechecker::{Predicate, ParseError, Implication, Type};
+
+
+
+fn smt(txt: &str) -> Predicate<f64> {
+    Predicate::from_smtlib(txt).unwrap()
+}
+
+
+
+#[test]
+fn round_trip() {
+    for txt in [
+        "(x > 5) && !(2 * y == -1) || enabled",
+        "(x - 3 * (y + 0.5) <= - z) && true",
+        "!(flag || false) && (x >= -2.25)",
+    ] {
+        let p = Predicate::from(txt).unwrap();
+        assert_eq!(smt(&p.to_smtlib()), p);
+    }
+
+    let p = Predicate::<BigRational>::parse("(x < -1/3) || (2/7 * y >= 0.5)").unwrap();
+    assert_eq!(Predicate::from_smtlib(&p.to_smtlib()), Ok(p));
+}
+
+
+#[test]
+fn integer_logic() {
+    let p = Predicate::<i64>::parse("(x > 5) && (y == -2)").unwrap();
+    assert_eq!(p.to_smtlib(), "(set-logic QF_LIA)\n(declare-const x Int)\n(declare-const y Int)\n(assert (and (> x 5) (= y (- 2))))\n(check-sat)\n");
+    assert_eq!(Predicate::from_smtlib(&p.to_smtlib()), Ok(p));
+
+    // the divisions must be exact
+    assert!(Predicate::<i64>::from_smtlib("(< x (/ 6 3))").is_ok());
+    assert!(Predicate::<i64>::from_smtlib("(< x (/ 1 3))").is_err());
+}
+
+
+#[test]
+fn sorts_of_declarations() {
+    let txt = "(declare-const x Int) (declare-const y Real) (declare-const b Bool) (assert (and (> x 5) (< y 1) b))";
+    let (p, schema) = Predicate::<f64>::from_smtlib_with_schema(txt).unwrap();
+    assert_eq!(p, smt(txt));
+    assert_eq!(schema.get_type("x"), Some(&Type::Int));
+    assert_eq!(schema.get_type("y"), Some(&Type::Real));
+    assert_eq!(schema.get_type("b"), Some(&Type::Bool));
+
+    // the integrality of `x` is kept in the schema
+    let goal = Predicate::from("x >= 6").unwrap();
+    assert_eq!(schema.implies(&p, &goal), Implication::Total);
+    assert_ne!(p.implies(&goal), Implication::Total);
+
+    // an integer type can not give the values of real arguments
+    let err = Predicate::<i64>::from_smtlib("(declare-const y Real) (assert (< y 1))").unwrap_err();
+    assert_eq!(err, ParseError::UnexpectedToken("Real".to_string(), *err.span()));
+    assert_eq!(err.span().start, 17);
+    assert!(Predicate::<i64>::from_smtlib("(declare-const x Int) (assert (< x 1))").is_ok());
+}
+
+
+#[test]
+fn implication_query() {
+    let a = Predicate::from("x > 5").unwrap();
+    let b = Predicate::from("(x > 2) && ok").unwrap();
+    assert_eq!(a.implication_to_smtlib(&b), "(set-logic QF_LRA)\n(declare-const ok Bool)\n(declare-const x Real)\n(assert (and (> x 5.0) (not (and (> x 2.0) ok))))\n(check-sat)\n");
+}
+
+
+#[test]
+fn quoted_symbols() {
+    let p = Predicate::from("(let > 1) || distinct").unwrap();
+    assert_eq!(p.to_smtlib().lines().nth(3), Some("(assert (or (> |let| 1.0) |distinct|))"));
+    assert_eq!(smt(&p.to_smtlib()), p);
+}
+
+
+#[test]
+fn operators() {
+    let equivalent = |txt: &str, expected: &str| assert!(smt(txt).equivalent(&Predicate::from(expected).unwrap()), "{txt}");
+
+    equivalent("(<= 0 x y 10)", "(0 <= x) && (x <= y) && (y <= 10)");
+    equivalent("(distinct x y 0)", "!(x == y) && !(x == 0) && !(y == 0)");
+    equivalent("(=> a b c)", "! a || ! b || c");
+    equivalent("(xor a (> x 1))", "(a && (x <= 1)) || (! a && (x > 1))");
+    equivalent("(ite a (> x 1) (< x 0))", "(a && (x > 1)) || (! a && (x < 0))");
+    equivalent("(declare-const a Bool) (declare-const b Bool) (assert (= a b))", "(a && b) || (! a && ! b)");
+    equivalent("(= a b)", "a == b");
+    equivalent("(and)", "true");
+    equivalent("(> (* 2 (- x) (/ 1 4)) (- 3 y 1.5))", "- 0.5 * x > 1.5 - y");
+    equivalent("(> (/ x 4) (to_real 1))", "x > 4");
+
+    // comments, strings and ignored commands
+    equivalent("(set-info :source |a \"b\" c|) ; a comment\n(set-info :note \"x \"\"y\"\"\")\n(assert (> x 0))\n(assert b)\n(check-sat)\n(exit)", "(x > 0) && b");
+}
+
+
+#[test]
+fn errors() {
+    let err = |txt: &str| Predicate::<f64>::from_smtlib(txt).unwrap_err();
+
+    assert!(matches!(err("(assert (> x 0)"), ParseError::UnbalancedParenthesis(_)));
+    assert!(matches!(err("(> x 0))"), ParseError::UnbalancedParenthesis(_)));
+    assert!(matches!(err("(> (* x y) 0)"), ParseError::NonLinearProduct(_)));
+    assert!(matches!(err("(> (mod x 2) 0)"), ParseError::UnknownOperator(..)));
+    assert!(matches!(err("(> x (/ 1 0))"), ParseError::NotANumber(_)));
+    assert!(matches!(err("(not)"), ParseError::MissingOperand(..)));
+    assert!(matches!(err("(declare-const b Bool) (assert (> b 1))"), ParseError::WrongType(..)));
+    assert!(matches!(err("(declare-const x Real) (assert (and x))"), ParseError::WrongType(..)));
+    assert!(matches!(err("(declare-fun f (Real) Real)"), ParseError::UnexpectedToken(..)));
+    assert!(matches!(err("(push 1)"), ParseError::UnexpectedToken(..)));
+
+    let txt = "(assert\n  (> x |y)";
+    assert_eq!(err(txt).span().line, 2);
+}
@@ -0,0 +1,77 @@
+//! Conversion of predicates from and to the conditions of SQL `WHERE` clauses.
+
+mod common;
+
+use predicatechecker::{Predicate, Value, Placeholder, ParseError, var};
+use common::parse;
+
+
+
+#[test]
+fn round_trip() {
+    for txt in [
+        "(x > 5) && !(2 * y == -1) || enabled",
+        "(x - 3 * (y + 0.5) <= - z) && true && !(a && false)",
+        "!(flag || (x >= -2.25)) || (x < 1 || (y > 2))",
+        "x - (y - z) == - (a + 1) * 2",
+    ] {
+        let p = parse(txt);
+        for placeholder in [Placeholder::QuestionMark, Placeholder::Numbered] {
+            let (sql, params) = p.to_sql(placeholder);
+            assert_eq!(Predicate::from_sql(&sql, &params), Ok(p.clone()), "{sql}");
+        }
+    }
+}
+
+
+#[test]
+fn export() {
+    let (sql, params) = parse("(x - (y - 1) >= 2 * - z) || ! ok").to_sql(Placeholder::QuestionMark);
+    assert_eq!(sql, "\"x\" - (\"y\" - ?) >= ? * -\"z\" OR NOT \"ok\"");
+    assert_eq!(params, vec![1.0, 2.0]);
+
+    // a double negation is not a comment
+    let y = Value::Neg(Box::new(Value::Neg(Box::new(Value::Arg("y".to_string())))));
+    let p = Predicate::GreaterThan(y, Value::Literal(0.0));
+    let (sql, params) = p.to_sql(Placeholder::QuestionMark);
+    assert_eq!(sql, "-(-\"y\") > ?");
+    assert_eq!(Predicate::from_sql(&sql, &params), Ok(p));
+
+    let p = Predicate::<i64>::BoolArg("say \"hi\"".to_string());
+    assert_eq!(p.to_sql(Placeholder::Numbered), ("\"say \"\"hi\"\"\"".to_string(), vec![]));
+    assert_eq!(Predicate::from_sql("\"say \"\"hi\"\"\"", &[]), Ok(p));
+}
+
+
+#[test]
+fn import() {
+    let sql = |txt: &str| Predicate::<f64>::from_sql(txt, &[1.0, 2.0]).unwrap();
+
+    assert_eq!(sql("x <> 1 AND y != ?"), parse("!(x == 1) && !(y == 1)"));
+    assert_eq!(sql("NOT x BETWEEN $2 AND 3"), parse("!((x >= 2) && (x <= 3))"));
+    assert_eq!(sql("x NOT BETWEEN -1 AND 3.5"), parse("!((x >= -1) && (x <= 3.5))"));
+    assert_eq!(sql("x in (1, ?, $1) or not active"), parse("x in {1, 1, 1} || ! active"));
+    assert_eq!(sql("x IN (1, y)"), parse("(x == 1) || (x == y)"));
+    assert_eq!(sql("`Total Price` NOT IN (?)"), var("Total Price").not_in([1.0]));
+    assert_eq!(sql("(a + b) * 2 > 3 AND (c OR (d AND TRUE))"), parse("((a + b) * 2 > 3) && (c || (d && true))"));
+    assert_eq!(sql("a OR b AND c"), parse("a || (b && c)"));
+    assert_eq!(sql("((x)) >= -(y)"), parse("x >= - y"));
+}
+
+
+#[test]
+fn errors() {
+    let err = |txt: &str| Predicate::<f64>::from_sql(txt, &[1.0]).unwrap_err();
+
+    assert!(matches!(err("x > ? AND y < ?"), ParseError::MissingOperand(..)));
+    assert!(matches!(err("x * y > 1"), ParseError::NonLinearProduct(_)));
+    assert!(matches!(err("(x > 1"), ParseError::UnbalancedParenthesis(_)));
+    assert!(matches!(err("x > 1)"), ParseError::UnbalancedParenthesis(_)));
+    assert!(matches!(err("x + 1"), ParseError::UnexpectedToken(..)));
+    assert!(matches!(err("x LIKE 'a%'"), ParseError::UnexpectedToken(..)));
+    assert!(matches!(err("x BETWEEN 1 OR 2"), ParseError::UnexpectedToken(..)));
+    assert!(matches!(err("x > $0"), ParseError::UnexpectedToken(..)));
+
+    let e = err("x > 1 AND\n  y >");
+    assert_eq!((e.span().line, e.span().column), (2, 6));
+}
@@ -0,0 +1,96 @@
+//! Predicates over the different supported numerical types.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use num::{BigInt, BigRational, Num, Signed, ToPrimitive};
+use predicatechecker::{Predicate, Implication, ParseError, EvalError, Domain, Checked};
+
+
+
+fn parse<T: Num + PartialOrd + Clone + FromStr>(txt: &str) -> Predicate<T> {
+    Predicate::parse(txt).unwrap()
+}
+
+
+/// Check the reasoning which gives the same results with every signed type.
+fn check_reasoning<T: Num + Signed + PartialOrd + Clone + FromStr + ToPrimitive + Display + Debug + Checked>() {
+    let a = parse::<T>("x > 5");
+    assert_eq!(a.implies(&parse("x > 4")), Implication::Total);
+    assert_eq!(a.implies(&parse("x < 0")), Implication::Inexistant);
+
+    // negations of values and of differences
+    assert_eq!(parse::<T>("-x > 2").implies(&parse("x < 0")), Implication::Total);
+    assert_eq!(parse::<T>("(x - y > 0) && (y > 3)").implies(&parse("x > 3")), Implication::Total);
+    assert!(parse::<T>("(x - y > 0) && (y - x > 0)").is_contradiction());
+    assert_eq!(parse::<T>("(-x < -1) && (x <= 8)").get_domain("x"), parse("(x > 1) && (x <= 8)").get_domain("x"));
+}
+
+
+#[test]
+fn reasoning_with_each_type() {
+    check_reasoning::<f32>();
+    check_reasoning::<f64>();
+    check_reasoning::<i32>();
+    check_reasoning::<i64>();
+    check_reasoning::<BigInt>();
+    check_reasoning::<BigRational>();
+}
+
+
+#[test]
+fn unsigned_types_without_reasoning() {
+    // the reasoning negates values, but parsing, displaying, evaluating and normal forms do not
+    let p = parse::<u64>("(x > 5) && !(y == 18446744073709551615)");
+    assert_eq!(p.to_string(), "x > 5 && !(y == 18446744073709551615)");
+    assert_eq!(p.get_arguments(), parse::<u64>("x + y > 0").get_arguments());
+    assert_eq!(parse::<u64>("!(x > 5)").to_nnf(), parse("x <= 5"));
+
+    let values = |x: u64, y: u64| HashMap::from([("x".to_string(), x), ("y".to_string(), y)]);
+    assert_eq!(p.evaluate(&values(6, 0)), Ok(true));
+    assert_eq!(p.evaluate(&values(6, u64::MAX)), Ok(false));
+    assert_eq!(parse::<u64>("x - 1 < y").evaluate(&values(0, 0)), Err(EvalError::Overflow("x - 1".to_string())));
+}
+
+
+#[test]
+fn literals_are_read_by_the_type() {
+    // too large for a f64, but exact with a i64 or a BigInt
+    let a = parse::<i64>("id == 9007199254740993");
+    assert_eq!(a.implies(&parse("id > 9007199254740992")), Implication::Total);
+    let a = parse::<BigInt>("id == 123456789012345678901234567890");
+    assert_eq!(a.implies(&parse("id > 123456789012345678901234567889")), Implication::Total);
+
+    // literals that the type can not represent
+    assert!(matches!(Predicate::<i64>::parse("x > 1.5"), Err(ParseError::UnexpectedToken(t, _)) if t == "1.5"));
+    assert!(matches!(Predicate::<i32>::parse("x > 9999999999"), Err(ParseError::UnexpectedToken(..))));
+    assert!(Predicate::<BigInt>::parse("x > 1/3").is_err());
+}
+
+
+#[test]
+fn integer_types_have_no_values_between_integers() {
+    assert_eq!(parse::<i32>("x > 5").implies(&parse("x >= 6")), Implication::Total);
+    assert_ne!(parse::<f32>("x > 5").implies(&parse("x >= 6")), Implication::Total);
+    assert!(parse::<BigInt>("(2 * x > 1) && (2 * x < 3) && (x != 1)").is_contradiction());
+    assert!(parse::<i64>("(x <= 4) || (x >= 5)").is_valid());
+    assert!(parse::<i64>("(x > 4) && (x < 5)").is_contradiction());
+    assert_eq!(parse::<i64>("(x < 5) || (x > 5)").get_domain("x"), parse("x != 5").get_domain("x"));
+    assert_eq!(parse::<i64>("(3 * x > 4) && (3 * x < 8)").get_domain("x"), parse("x == 2").get_domain("x"));
+}
+
+
+#[test]
+fn bounds_of_integer_types_do_not_overflow() {
+    assert!(!parse::<i64>("x > 9223372036854775807").is_satisfiable());
+    // x - i64::MIN can not be represented: like non-linear ones, the comparison can have any truth value
+    assert!(!parse::<i64>("x < -9223372036854775808").is_valid());
+    assert!(parse::<i64>("x >= 9223372036854775807").is_satisfiable());
+    assert_eq!(parse::<i64>("x > 9223372036854775806").implies(&parse("x == 9223372036854775807")), Implication::Total);
+    assert_eq!(parse::<i64>("x != 9223372036854775807").implies(&parse("x < 9223372036854775807")), Implication::Total);
+    assert_eq!(parse::<i8>("(x > 100) && (y > 100)").implies(&parse("x + y > 120")), Implication::Total);
+    assert!(parse::<i8>("(x > 100) && (x < 2 * y - 100)").is_satisfiable());
+    assert!(parse::<i8>("(x > 100) && (x - y < -100)").is_satisfiable());
+    assert_eq!(parse::<i64>("-x > 9223372036854775807").get_domain("x"), Domain::new(None, false, Some(i64::MIN), true));
+}
@@ -0,0 +1,69 @@
+//! Counterexamples given when an implication is not total.
+
+mod common;
+
+use predicatechecker::{Predicate, Implication, Assignment};
+use common::parse;
+
+
+
+/// Return the counterexample to the implication, checking that it verifies A but not B.
+fn witness(a: &str, b: &str) -> Assignment<f64> {
+    let (a, b) = (parse(a), parse(b));
+    let (implication, witness) = a.implies_with_witness(&b);
+    assert_ne!(implication, Implication::Total);
+
+    let w = witness.unwrap();
+    assert_eq!(a.evaluate(&w), Ok(true));
+    assert_eq!(b.evaluate(&w), Ok(false));
+    w
+}
+
+
+
+#[test]
+fn no_witness_for_total_implications() {
+    assert_eq!(parse("x > 5").implies_with_witness(&parse("x > 4")), (Implication::Total, None));
+    assert_eq!(parse("(x < y) && (y < 0)").implies_with_witness(&parse("x < 0")), (Implication::Total, None));
+}
+
+
+#[test]
+fn numerical_arguments() {
+    let w = witness("x > 5", "x > 5.5");
+    assert!(*w.get_value("x").unwrap() <= 5.5);
+
+    witness("(x < y) && (y < 10)", "x < 5");
+    witness("(x + y == 10) && (x >= 0)", "y < 10");
+    witness("x in {1, 2, 3}", "x < 3");
+}
+
+
+#[test]
+fn every_argument_has_a_value() {
+    // z is not constrained by A, but B uses it
+    let w = witness("(x > 0) && enabled", "(x > 0) && (z > 0)");
+    assert!(w.get_value("x").is_some() && w.get_value("z").is_some());
+    assert_eq!(w.get_bool("enabled"), Some(true));
+
+    let w = witness("enabled || (x > 0)", "enabled");
+    assert_eq!(w.get_bool("enabled"), Some(false));
+    assert!(*w.get_value("x").unwrap() > 0.0);
+}
+
+
+#[test]
+fn contradictions_have_no_witness() {
+    let (_, witness) = Predicate::<f64>::False.implies_with_witness(&parse("x > 0"));
+    assert_eq!(witness, None);
+}
+
+
+#[test]
+fn integer_witness_can_be_missing() {
+    // values exist among the real numbers, but none of them is made of integers
+    let a = Predicate::<i64>::parse("(11 * x + 13 * y >= 27) && (11 * x + 13 * y <= 45) && (7 * x - 9 * y >= -10) && (7 * x - 9 * y <= 4)").unwrap();
+    let (implication, witness) = a.implies_with_witness(&Predicate::parse("y < 2").unwrap());
+    assert_eq!(implication, Implication::Partial { coverage: None });
+    assert_eq!(witness, None);
+}